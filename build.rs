@@ -0,0 +1,8 @@
+fn main() {
+  println!("cargo:rerun-if-changed=build.rs");
+
+  // Recompile the macros, and therefore all crates using them, when the fixed compile time changes.
+  if std::env::var_os("CARGO_FEATURE_DETERMINISTIC").is_some() {
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+  }
+}
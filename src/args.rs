@@ -0,0 +1,482 @@
+use proc_macro2::Span;
+use syn::{
+  parse::{Parse, ParseStream},
+  Error, Ident, LitStr, Result, Token,
+};
+use time::{
+  format_description::well_known::Rfc3339, macros::format_description, Date, OffsetDateTime, Time, UtcOffset,
+};
+
+/// Sub-second precision of emitted `time::Time` values.
+#[cfg(feature = "time-types")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Precision {
+  #[default]
+  Seconds,
+  Millis,
+  Micros,
+  Nanos,
+}
+
+#[cfg(feature = "time-types")]
+impl Parse for Precision {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let ident: Ident = input.parse()?;
+
+    Ok(match ident.to_string().as_str() {
+      "seconds" => Self::Seconds,
+      "millis" => Self::Millis,
+      "micros" => Self::Micros,
+      "nanos" => Self::Nanos,
+      _ => return Err(Error::new(ident.span(), "expected one of `seconds`, `millis`, `micros` or `nanos`")),
+    })
+  }
+}
+
+/// Granularity to which emitted date and time values are truncated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+  Second,
+  Minute,
+  Hour,
+  Day,
+}
+
+impl Granularity {
+  pub fn truncate(self, datetime: OffsetDateTime) -> OffsetDateTime {
+    let (hour, minute, second) = datetime.to_hms();
+
+    let time = match self {
+      Self::Second => Time::from_hms(hour, minute, second),
+      Self::Minute => Time::from_hms(hour, minute, 0),
+      Self::Hour => Time::from_hms(hour, 0, 0),
+      Self::Day => Ok(Time::MIDNIGHT),
+    };
+
+    datetime.replace_time(time.unwrap())
+  }
+}
+
+impl Parse for Granularity {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let ident: Ident = input.parse()?;
+
+    Ok(match ident.to_string().as_str() {
+      "second" => Self::Second,
+      "minute" => Self::Minute,
+      "hour" => Self::Hour,
+      "day" => Self::Day,
+      _ => return Err(Error::new(ident.span(), "expected one of `second`, `minute`, `hour` or `day`")),
+    })
+  }
+}
+
+/// Fixed UTC offset given as string literal in `±hh:mm` format.
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetLit {
+  pub offset: UtcOffset,
+  #[cfg_attr(not(feature = "time-types"), allow(dead_code))]
+  pub span: Span,
+}
+
+impl Parse for OffsetLit {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let lit: LitStr = input.parse()?;
+
+    match UtcOffset::parse(&lit.value(), format_description!("[offset_hour sign:mandatory]:[offset_minute]")) {
+      Ok(offset) => Ok(Self { offset, span: lit.span() }),
+      Err(_) => Err(Error::new(lit.span(), "expected a UTC offset in `±hh:mm` format")),
+    }
+  }
+}
+
+/// Apply the `offset` and `granularity` arguments to the given date and time.
+fn apply(datetime: OffsetDateTime, offset: Option<OffsetLit>, granularity: Option<Granularity>) -> OffsetDateTime {
+  let datetime = offset.map_or(datetime, |offset| datetime.to_offset(offset.offset));
+  granularity.map_or(datetime, |granularity| granularity.truncate(datetime))
+}
+
+/// Arguments accepted by macros emitting a time of day.
+#[cfg(feature = "time-types")]
+#[derive(Debug, Default)]
+pub struct TimeArgs {
+  pub precision: Precision,
+  pub granularity: Option<Granularity>,
+  pub offset: Option<OffsetLit>,
+}
+
+#[cfg(feature = "time-types")]
+impl TimeArgs {
+  pub fn apply(&self, datetime: OffsetDateTime) -> OffsetDateTime {
+    apply(datetime, self.offset, self.granularity)
+  }
+
+  /// Fail if an `offset` was given to a macro which determines the offset itself.
+  pub fn reject_offset(&self) -> Result<()> {
+    match self.offset {
+      Some(offset) => Err(Error::new(offset.span, "`offset` is not supported by this macro")),
+      None => Ok(()),
+    }
+  }
+}
+
+#[cfg(feature = "time-types")]
+impl Parse for TimeArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let mut args = Self::default();
+
+    while !input.is_empty() {
+      let key: Ident = input.parse()?;
+      input.parse::<Token![=]>()?;
+
+      match key.to_string().as_str() {
+        "precision" => args.precision = input.parse()?,
+        "granularity" => args.granularity = Some(input.parse()?),
+        "offset" => args.offset = Some(input.parse()?),
+        _ => return Err(Error::new(key.span(), format!("unknown argument `{key}`"))),
+      }
+
+      if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+      }
+    }
+
+    Ok(args)
+  }
+}
+
+/// Well-known formats of the `time` crate selectable in `datetime_str!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WellKnownFormat {
+  Rfc2822,
+  Rfc3339,
+  Iso8601,
+}
+
+impl Parse for WellKnownFormat {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let ident: Ident = input.parse()?;
+
+    Ok(match ident.to_string().as_str() {
+      "Rfc2822" => Self::Rfc2822,
+      "Rfc3339" => Self::Rfc3339,
+      "Iso8601" => Self::Iso8601,
+      _ => return Err(Error::new(ident.span(), "expected one of `Rfc2822`, `Rfc3339` or `Iso8601`")),
+    })
+  }
+}
+
+/// Arguments accepted by `datetime_str!`.
+#[derive(Debug, Default)]
+pub struct DatetimeStrArgs {
+  pub format: Option<WellKnownFormat>,
+  pub granularity: Option<Granularity>,
+  pub offset: Option<OffsetLit>,
+}
+
+impl DatetimeStrArgs {
+  pub fn apply(&self, datetime: OffsetDateTime) -> OffsetDateTime {
+    apply(datetime, self.offset, self.granularity)
+  }
+}
+
+impl Parse for DatetimeStrArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let mut args = Self::default();
+
+    if !input.is_empty() && !input.peek2(Token![=]) {
+      args.format = Some(input.parse()?);
+
+      if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+      }
+    }
+
+    while !input.is_empty() {
+      let key: Ident = input.parse()?;
+      input.parse::<Token![=]>()?;
+
+      match key.to_string().as_str() {
+        "granularity" => args.granularity = Some(input.parse()?),
+        "offset" => args.offset = Some(input.parse()?),
+        _ => return Err(Error::new(key.span(), format!("unknown argument `{key}`"))),
+      }
+
+      if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+      }
+    }
+
+    Ok(args)
+  }
+}
+
+/// Duration given as a list of `unit = amount` arguments, e.g. `days = 90`.
+#[cfg(feature = "time-types")]
+#[derive(Debug)]
+pub struct DurationArgs {
+  pub duration: time::Duration,
+}
+
+#[cfg(feature = "time-types")]
+impl Parse for DurationArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    if input.is_empty() {
+      return Err(input.error("expected a duration, e.g. `days = 90`"));
+    }
+
+    let mut seconds: i64 = 0;
+
+    while !input.is_empty() {
+      let key: Ident = input.parse()?;
+      input.parse::<Token![=]>()?;
+      let lit: syn::LitInt = input.parse()?;
+      let amount: i64 = lit.base10_parse()?;
+
+      let unit_seconds = match key.to_string().as_str() {
+        "weeks" => 604_800,
+        "days" => 86_400,
+        "hours" => 3_600,
+        "minutes" => 60,
+        "seconds" => 1,
+        _ => {
+          return Err(Error::new(
+            key.span(),
+            format!("unknown argument `{key}`, expected one of `weeks`, `days`, `hours`, `minutes` or `seconds`"),
+          ))
+        },
+      };
+
+      seconds = amount
+        .checked_mul(unit_seconds)
+        .and_then(|amount_seconds| seconds.checked_add(amount_seconds))
+        .ok_or_else(|| Error::new(lit.span(), "duration is out of range"))?;
+
+      if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+      }
+    }
+
+    Ok(Self { duration: time::Duration::seconds(seconds) })
+  }
+}
+
+/// Date or date and time given as string literal, either in `yyyy-MM-dd` or in RFC 3339 format.
+///
+/// Dates are interpreted as midnight UTC.
+pub struct DatetimeLit {
+  pub datetime: OffsetDateTime,
+  pub lit: LitStr,
+}
+
+impl Parse for DatetimeLit {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let lit: LitStr = input.parse()?;
+    let value = lit.value();
+
+    let datetime = if let Ok(date) = Date::parse(&value, format_description!("[year]-[month]-[day]")) {
+      date.midnight().assume_utc()
+    } else if let Ok(datetime) = OffsetDateTime::parse(&value, &Rfc3339) {
+      datetime
+    } else {
+      return Err(Error::new(
+        lit.span(),
+        "expected a date in `yyyy-MM-dd` format or a date and time in RFC 3339 format",
+      ));
+    };
+
+    Ok(Self { datetime, lit })
+  }
+}
+
+/// Arguments accepted by `assert_build_before!`.
+pub struct AssertBuildBeforeArgs {
+  pub deadline: DatetimeLit,
+  pub message: Option<LitStr>,
+}
+
+impl Parse for AssertBuildBeforeArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let deadline = input.parse()?;
+
+    let mut message = None;
+    if !input.is_empty() {
+      input.parse::<Token![,]>()?;
+
+      if !input.is_empty() {
+        message = Some(input.parse()?);
+        input.parse::<Option<Token![,]>>()?;
+      }
+    }
+
+    Ok(Self { deadline, message })
+  }
+}
+
+/// Optional epoch given as string literal, defaulting to the UNIX epoch.
+pub struct EpochArgs {
+  pub epoch: OffsetDateTime,
+}
+
+impl Parse for EpochArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    if input.is_empty() {
+      return Ok(Self { epoch: OffsetDateTime::UNIX_EPOCH });
+    }
+
+    let DatetimeLit { datetime, .. } = input.parse()?;
+    Ok(Self { epoch: datetime })
+  }
+}
+
+/// Arguments accepted by `duration_since!`.
+pub struct DurationSinceArgs {
+  pub epoch: DatetimeLit,
+  /// Whether to emit a `core::time::Duration` instead of a `time::Duration`.
+  pub core: bool,
+}
+
+impl Parse for DurationSinceArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let epoch = input.parse()?;
+
+    let mut core = false;
+    if !input.is_empty() {
+      input.parse::<Token![,]>()?;
+
+      if !input.is_empty() {
+        let ty: Ident = input.parse()?;
+        if ty != "core" {
+          return Err(Error::new(ty.span(), "expected `core`"));
+        }
+        core = true;
+        input.parse::<Option<Token![,]>>()?;
+      }
+    }
+
+    Ok(Self { epoch, core })
+  }
+}
+
+/// Arguments accepted by `licensed_until!`.
+pub struct LicensedUntilArgs {
+  pub expiry: DatetimeLit,
+  pub grace_days: i64,
+}
+
+impl Parse for LicensedUntilArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let expiry = input.parse()?;
+
+    let mut grace_days = 0;
+    while !input.is_empty() {
+      input.parse::<Token![,]>()?;
+      if input.is_empty() {
+        break;
+      }
+
+      let key: Ident = input.parse()?;
+      input.parse::<Token![=]>()?;
+
+      match key.to_string().as_str() {
+        "grace_days" => grace_days = input.parse::<syn::LitInt>()?.base10_parse()?,
+        _ => return Err(Error::new(key.span(), format!("unknown argument `{key}`"))),
+      }
+    }
+
+    Ok(Self { expiry, grace_days })
+  }
+}
+
+/// Arguments accepted by `assert_same_rustc_as!`.
+pub enum AssertSameRustcArgs {
+  /// Expected version given as string literal.
+  Version(LitStr),
+  /// Name of the environment variable containing the expected version, given as `env = "NAME"`.
+  Env(LitStr),
+}
+
+impl Parse for AssertSameRustcArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let args = if input.peek(Ident) {
+      let key: Ident = input.parse()?;
+      if key != "env" {
+        return Err(Error::new(key.span(), format!("unknown argument `{key}`")));
+      }
+      input.parse::<Token![=]>()?;
+      Self::Env(input.parse()?)
+    } else {
+      Self::Version(input.parse()?)
+    };
+
+    input.parse::<Option<Token![,]>>()?;
+    Ok(args)
+  }
+}
+
+/// Arguments accepted by `#[cfg_rustc]`, each converted into a version requirement.
+pub struct CfgRustcArgs {
+  pub requirements: Vec<semver::VersionReq>,
+}
+
+impl Parse for CfgRustcArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    if input.is_empty() {
+      return Err(input.error("expected at least one of `since`, `before` or `matches`"));
+    }
+
+    let mut requirements = Vec::new();
+
+    while !input.is_empty() {
+      let key: Ident = input.parse()?;
+      input.parse::<Token![=]>()?;
+      let lit: LitStr = input.parse()?;
+
+      let requirement = match key.to_string().as_str() {
+        "since" => format!(">={}", lit.value()),
+        "before" => format!("<{}", lit.value()),
+        "matches" => lit.value(),
+        _ => {
+          return Err(Error::new(
+            key.span(),
+            format!("unknown argument `{key}`, expected one of `since`, `before` or `matches`"),
+          ))
+        },
+      };
+
+      match semver::VersionReq::parse(&requirement) {
+        Ok(requirement) => requirements.push(requirement),
+        Err(err) => return Err(Error::new(lit.span(), format!("invalid version `{}`: {err}", lit.value()))),
+      }
+
+      if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+      }
+    }
+
+    Ok(Self { requirements })
+  }
+}
+
+/// Optional `fallback = "..."` argument used if information cannot be retrieved.
+pub struct FallbackArgs {
+  pub fallback: Option<LitStr>,
+}
+
+impl Parse for FallbackArgs {
+  fn parse(input: ParseStream) -> Result<Self> {
+    if input.is_empty() {
+      return Ok(Self { fallback: None });
+    }
+
+    let key: Ident = input.parse()?;
+    if key != "fallback" {
+      return Err(Error::new(key.span(), format!("unknown argument `{key}`")));
+    }
+    input.parse::<Token![=]>()?;
+    let fallback = input.parse()?;
+    input.parse::<Option<Token![,]>>()?;
+
+    Ok(Self { fallback: Some(fallback) })
+  }
+}
@@ -1,13 +1,55 @@
 //! This crate provides macros for getting compile time information.
 //!
 //! You can get the compile time either as
-//! [`time::Date`](time::Date), [`time::Time`](time::Time),
-//! [`time::OffsetDateTime`](time::OffsetDateTime), string, or UNIX timestamp.
+//! [`time::Date`], [`time::Time`],
+//! [`time::OffsetDateTime`], string, or UNIX timestamp.
+//! The individual date and time components are also available as integer literals.
+//!
+//! The build machine's local UTC offset is available as
+//! [`time::UtcOffset`] or string.
 //!
 //! You can get the Rust compiler version either as
-//! [`semver::Version`](semver::Version) or string,
+//! [`semver::Version`] or string,
 //! and the individual version parts as integer literals or strings, respectively.
 //!
+//! Items can be conditionally compiled depending on the Rust compiler version using
+//! [`#[cfg_rustc]`](macro@cfg_rustc).
+//!
+//! # Features
+//!
+//! - `time-types` (enabled by default): Macros expanding to types of the `time` crate, e.g.
+//!   `date!`, `time!` or `datetime!`. Only these macros require a dependency on the `time` crate in
+//!   your own `Cargo.toml`. Disable this feature if you only need macros expanding to plain string or
+//!   integer literals. If you depend on `compile-time` with `default-features = false`, you need to
+//!   enable this feature explicitly to keep using these macros. Note that the macros still use the
+//!   `time` crate internally, so it remains in your dependency tree and `Cargo.lock` as a
+//!   dependency of `compile-time`.
+//! - `shared-time`: Share the compile time between all crates compiled by the same `cargo` invocation
+//!   instead of capturing it separately for every crate. The compile time is persisted in
+//!   `target/<profile>/compile-time/`. This is currently only supported on Unix and Windows, and
+//!   only if `cargo` runs `rustc` directly, i.e. not through a caching `RUSTC_WRAPPER` like `sccache`.
+//! - `tz`: Macros converting the compile time into a named time zone using a bundled copy of the
+//!   IANA time zone database, e.g. `datetime_in_tz!`.
+//! - `deterministic`: Fix the compile time to the value of the `SOURCE_DATE_EPOCH` environment variable,
+//!   or to the UNIX epoch if it is not set, and the local UTC offset to UTC, for reproducible builds.
+//!   This also applies to `now!` and `now_str!`. Takes precedence over `shared-time`. Crates using
+//!   the macros are recompiled when `SOURCE_DATE_EPOCH` changes.
+//! - `lenient`: Instead of failing to compile, macros depending on the build environment fall back
+//!   to default values when information cannot be retrieved, i.e. the Rust compiler version falls
+//!   back to `0.0.0` and its release channel to `dev`, the local UTC offset falls back to UTC,
+//!   the shared compile time falls back to the compile time of the current crate and an invalid
+//!   `SOURCE_DATE_EPOCH` falls back to the UNIX epoch. Assertions on the Rust compiler version are
+//!   skipped if it cannot be retrieved.
+//!
+//! # Snapshot
+//!
+//! When the `COMPILE_TIME_SNAPSHOT` environment variable is set to `1`, the values resolved during
+//! macro expansion are additionally written to
+//! `target/<profile>/compile-time/<package>/<crate>.<kind>.json` for every crate compiled while it
+//! is set, so external tooling can consume exactly the values embedded in the binary. `<kind>` is
+//! the crate type, e.g. `bin` or `lib`, or `test` for tests, so e.g. the binary of a package
+//! `app` built with `--release` has its snapshot in `target/release/compile-time/app/app.bin.json`.
+//!
 //! # Example
 //!
 //! ```
@@ -22,10 +64,341 @@ extern crate proc_macro;
 use once_cell::sync::Lazy;
 use proc_macro::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use time::{macros::format_description, OffsetDateTime};
+use syn::{parse_macro_input, Ident};
+use time::{
+  format_description::well_known::{Iso8601, Rfc2822, Rfc3339},
+  macros::format_description,
+  OffsetDateTime, UtcOffset,
+};
+
+mod args;
+#[cfg(feature = "shared-time")]
+mod shared;
+mod snapshot;
+mod target;
+use args::{
+  AssertBuildBeforeArgs, AssertSameRustcArgs, CfgRustcArgs, DatetimeLit, DatetimeStrArgs, DurationSinceArgs, EpochArgs,
+  FallbackArgs, LicensedUntilArgs, WellKnownFormat,
+};
+#[cfg(feature = "time-types")]
+use args::{DurationArgs, Precision, TimeArgs};
+
+static COMPILE_TIME: Lazy<Result<OffsetDateTime, String>> = Lazy::new(capture_compile_time);
+static LOCAL_OFFSET: Lazy<Result<UtcOffset, String>> = Lazy::new(|| {
+  let local_offset = if cfg!(feature = "deterministic") { UtcOffset::UTC } else { capture_local_offset()? };
+  snapshot::record_local_offset(local_offset)?;
+  Ok(local_offset)
+});
+static RUSTC_VERSION_META: Lazy<Result<rustc_version::VersionMeta, String>> = Lazy::new(|| {
+  let rustc_version_meta = rustc_version::version_meta().map_err(|err| {
+    format!(
+      "failed to get the Rust compiler version: {err}; make sure `rustc` is in your `PATH` or set \
+       the `RUSTC` environment variable, or enable the `lenient` feature of `compile-time`"
+    )
+  })?;
+  snapshot::record_rustc_version(&rustc_version_meta.semver)?;
+  Ok(rustc_version_meta)
+});
+
+fn capture_compile_time() -> Result<OffsetDateTime, String> {
+  let compile_time = match deterministic_compile_time()? {
+    Some(compile_time) => compile_time,
+    None => capture_shared_compile_time()?,
+  };
+  snapshot::record_compile_time(compile_time)?;
+  Ok(compile_time)
+}
+
+/// Get the fixed compile time if the `deterministic` feature is enabled.
+#[cfg(not(feature = "deterministic"))]
+fn deterministic_compile_time() -> Result<Option<OffsetDateTime>, String> {
+  Ok(None)
+}
+
+/// Get the fixed compile time if the `deterministic` feature is enabled.
+///
+/// This is the value of `SOURCE_DATE_EPOCH`, or the UNIX epoch if it is not set. Changes of
+/// `SOURCE_DATE_EPOCH` are tracked by the build script.
+#[cfg(feature = "deterministic")]
+fn deterministic_compile_time() -> Result<Option<OffsetDateTime>, String> {
+  let Ok(source_date_epoch) = std::env::var("SOURCE_DATE_EPOCH") else { return Ok(Some(OffsetDateTime::UNIX_EPOCH)) };
+
+  match source_date_epoch.trim().parse().ok().and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+  {
+    Some(compile_time) => Ok(Some(compile_time)),
+    #[cfg(feature = "lenient")]
+    None => Ok(Some(OffsetDateTime::UNIX_EPOCH)),
+    #[cfg(not(feature = "lenient"))]
+    None => Err(format!("invalid `SOURCE_DATE_EPOCH` `{source_date_epoch}`, expected a UNIX timestamp")),
+  }
+}
+
+#[cfg(not(feature = "shared-time"))]
+fn capture_shared_compile_time() -> Result<OffsetDateTime, String> {
+  Ok(OffsetDateTime::now_utc())
+}
+
+#[cfg(feature = "shared-time")]
+fn capture_shared_compile_time() -> Result<OffsetDateTime, String> {
+  match shared::compile_time() {
+    Ok(compile_time) => Ok(compile_time.unwrap_or_else(OffsetDateTime::now_utc)),
+    #[cfg(feature = "lenient")]
+    Err(_) => Ok(OffsetDateTime::now_utc()),
+    #[cfg(not(feature = "lenient"))]
+    Err(err) => Err(format!("failed to get the shared compile time: {err}")),
+  }
+}
+
+fn capture_local_offset() -> Result<UtcOffset, String> {
+  match UtcOffset::local_offset_at(COMPILE_TIME.clone()?) {
+    Ok(offset) => Ok(offset),
+    #[cfg(feature = "lenient")]
+    Err(_) => Ok(UtcOffset::UTC),
+    #[cfg(not(feature = "lenient"))]
+    Err(err) => Err(format!("failed to get the local UTC offset: {err}")),
+  }
+}
+
+/// Get the current time for macros evaluated separately for every invocation, or the fixed
+/// compile time if the `deterministic` feature is enabled.
+fn capture_now() -> syn::Result<OffsetDateTime> {
+  let compile_time =
+    deterministic_compile_time().map_err(|err| syn::Error::new(proc_macro2::Span::call_site(), err))?;
+  Ok(compile_time.unwrap_or_else(OffsetDateTime::now_utc))
+}
+
+fn get_compile_time() -> syn::Result<OffsetDateTime> {
+  COMPILE_TIME.clone().map_err(|err| syn::Error::new(proc_macro2::Span::call_site(), err))
+}
+
+fn local_offset() -> syn::Result<UtcOffset> {
+  LOCAL_OFFSET.clone().map_err(|err| syn::Error::new(proc_macro2::Span::call_site(), err))
+}
+
+/// Return early from a macro with a compile error if the given result is an error.
+macro_rules! try_compile {
+  ($result:expr) => {
+    match $result {
+      Ok(value) => value,
+      Err(err) => return TokenStream::from(syn::Error::to_compile_error(&err)),
+    }
+  };
+}
+
+fn get_rustc_version_meta() -> syn::Result<&'static rustc_version::VersionMeta> {
+  #[cfg(feature = "lenient")]
+  static UNKNOWN_RUSTC_VERSION_META: Lazy<rustc_version::VersionMeta> = Lazy::new(|| {
+    let rustc_version_meta = rustc_version::VersionMeta {
+      semver: rustc_version::Version::new(0, 0, 0),
+      commit_hash: None,
+      commit_date: None,
+      build_date: None,
+      channel: rustc_version::Channel::Dev,
+      host: String::new(),
+      short_version_string: String::new(),
+      llvm_version: None,
+    };
+    // Record the fallback version, since it is the one embedded. This cannot fail with `lenient`.
+    let _ = snapshot::record_rustc_version(&rustc_version_meta.semver);
+    rustc_version_meta
+  });
+
+  match &*RUSTC_VERSION_META {
+    Ok(rustc_version_meta) => Ok(rustc_version_meta),
+    #[cfg(feature = "lenient")]
+    Err(_) => Ok(&UNKNOWN_RUSTC_VERSION_META),
+    #[cfg(not(feature = "lenient"))]
+    Err(err) => Err(syn::Error::new(proc_macro2::Span::call_site(), err)),
+  }
+}
+
+fn get_rustc_version() -> syn::Result<&'static rustc_version::Version> {
+  get_rustc_version_meta().map(|rustc_version_meta| &rustc_version_meta.semver)
+}
+
+/// Get the Rust compiler version, or `None` if it cannot be retrieved and the `lenient` feature is enabled.
+///
+/// Used by macros which cannot give a meaningful result for the `0.0.0` fallback version.
+fn get_known_rustc_version() -> syn::Result<Option<&'static rustc_version::Version>> {
+  if cfg!(feature = "lenient") && RUSTC_VERSION_META.is_err() {
+    return Ok(None);
+  }
+
+  get_rustc_version().map(Some)
+}
+
+/// Version without pre-release and build metadata, for matching against version requirements.
+fn release_version(version: &rustc_version::Version) -> rustc_version::Version {
+  rustc_version::Version::new(version.major, version.minor, version.patch)
+}
+
+fn version_tokens(version: &rustc_version::Version) -> proc_macro2::TokenStream {
+  let rustc_version::Version { major, minor, patch, pre, build } = version;
+
+  let pre = if pre.is_empty() {
+    quote! { ::semver::Prerelease::EMPTY }
+  } else {
+    let pre = pre.as_str();
+    quote! {
+      if let Ok(pre) = ::semver::Prerelease::new(#pre) {
+        pre
+      } else {
+        ::core::unreachable!()
+      }
+    }
+  };
+
+  let build = if build.is_empty() {
+    quote! { ::semver::BuildMetadata::EMPTY }
+  } else {
+    let build = build.as_str();
+    quote! {
+      if let Ok(build) = ::semver::BuildMetadata::new(#build) {
+        build
+      } else {
+        ::core::unreachable!()
+      }
+    }
+  };
+
+  quote! {
+    ::semver::Version {
+      major: #major,
+      minor: #minor,
+      patch: #patch,
+      pre: #pre,
+      build: #build,
+    }
+  }
+}
+
+/// Year of the given date and time for integer literals in `yyyy…` format.
+fn slug_year(datetime: OffsetDateTime) -> syn::Result<u64> {
+  match u64::try_from(datetime.year()) {
+    Ok(year) if year <= 9999 => Ok(year),
+    _ => Err(syn::Error::new(proc_macro2::Span::call_site(), "compile time year is out of range, expected 0 to 9999")),
+  }
+}
+
+/// Year of the given date and time for `u16` literals in tuples.
+fn tuple_year(datetime: OffsetDateTime) -> syn::Result<u16> {
+  u16::try_from(datetime.year()).map_err(|_| {
+    syn::Error::new(proc_macro2::Span::call_site(), "compile time year is out of range, expected 0 to 65535")
+  })
+}
+
+#[cfg(feature = "time-types")]
+fn date_tokens(date: time::Date) -> proc_macro2::TokenStream {
+  let year = date.year();
+  let month = format_ident!("{}", format!("{:?}", date.month()));
+  let day = date.day();
+
+  quote! {
+    match ::time::Date::from_calendar_date(#year, ::time::Month::#month, #day) {
+      Ok(date) => date,
+      _ => ::core::unreachable!(),
+    }
+  }
+}
+
+#[cfg(feature = "time-types")]
+fn offset_tokens(offset: UtcOffset) -> proc_macro2::TokenStream {
+  let (hours, minutes, seconds) = offset.as_hms();
+
+  quote! {
+    match ::time::UtcOffset::from_hms(#hours, #minutes, #seconds) {
+      Ok(offset) => offset,
+      _ => ::core::unreachable!(),
+    }
+  }
+}
+
+#[cfg(feature = "time-types")]
+fn time_tokens(time: time::Time, precision: Precision) -> proc_macro2::TokenStream {
+  let hour = time.hour();
+  let minute = time.minute();
+  let second = time.second();
+
+  let constructor = match precision {
+    Precision::Seconds => quote! { from_hms(#hour, #minute, #second) },
+    Precision::Millis => {
+      let millisecond = time.millisecond();
+      quote! { from_hms_milli(#hour, #minute, #second, #millisecond) }
+    },
+    Precision::Micros => {
+      let microsecond = time.microsecond();
+      quote! { from_hms_micro(#hour, #minute, #second, #microsecond) }
+    },
+    Precision::Nanos => {
+      let nanosecond = time.nanosecond();
+      quote! { from_hms_nano(#hour, #minute, #second, #nanosecond) }
+    },
+  };
+
+  quote! {
+    match ::time::Time::#constructor {
+      Ok(time) => time,
+      _ => ::core::unreachable!(),
+    }
+  }
+}
+
+#[cfg(feature = "time-types")]
+fn datetime_tokens(datetime: OffsetDateTime, precision: Precision) -> proc_macro2::TokenStream {
+  let date = date_tokens(datetime.date());
+  let time = time_tokens(datetime.time(), precision);
+
+  if datetime.offset().is_utc() {
+    quote! {
+      ::time::PrimitiveDateTime::new(#date, #time).assume_utc()
+    }
+  } else {
+    let offset = offset_tokens(datetime.offset());
+
+    quote! {
+      ::time::PrimitiveDateTime::new(#date, #time).assume_offset(#offset)
+    }
+  }
+}
+
+fn format_datetime(datetime: OffsetDateTime, format: Option<WellKnownFormat>) -> syn::Result<String> {
+  let datetime = datetime.replace_nanosecond(0).unwrap();
+
+  match format {
+    None if !datetime.offset().is_utc() => return format_offset_datetime(datetime),
+    None => {
+      let fmt = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]Z");
+      datetime.format(&fmt)
+    },
+    Some(WellKnownFormat::Rfc2822) => datetime.format(&Rfc2822),
+    Some(WellKnownFormat::Rfc3339) => datetime.format(&Rfc3339),
+    Some(WellKnownFormat::Iso8601) => datetime.format(&Iso8601::DEFAULT),
+  }
+  .map_err(format_error)
+}
+
+fn format_offset_datetime(datetime: OffsetDateTime) -> syn::Result<String> {
+  let fmt =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second][offset_hour sign:mandatory]:[offset_minute]");
+  datetime.format(&fmt).map_err(format_error)
+}
+
+/// Formatting fails e.g. for RFC 2822 if the year is before 1900, which is reachable with `SOURCE_DATE_EPOCH`.
+fn format_error(err: time::error::Format) -> syn::Error {
+  syn::Error::new(proc_macro2::Span::call_site(), format!("failed to format the compile time: {err}"))
+}
+
+#[cfg(feature = "tz")]
+fn tz_offset(tz: &syn::LitStr) -> syn::Result<UtcOffset> {
+  let time_zone = tzdb::tz_by_name(tz.value())
+    .ok_or_else(|| syn::Error::new(tz.span(), format!("unknown time zone `{}`", tz.value())))?;
+
+  let local_time_type = time_zone
+    .find_local_time_type(get_compile_time()?.unix_timestamp())
+    .map_err(|err| syn::Error::new(tz.span(), err))?;
 
-static COMPILE_TIME: Lazy<OffsetDateTime> = Lazy::new(OffsetDateTime::now_utc);
-static RUSTC_VERSION: Lazy<rustc_version::Result<rustc_version::Version>> = Lazy::new(rustc_version::version);
+  UtcOffset::from_whole_seconds(local_time_type.ut_offset()).map_err(|err| syn::Error::new(tz.span(), err))
+}
 
 /// Compile date as `time::Date`.
 ///
@@ -39,21 +412,13 @@ static RUSTC_VERSION: Lazy<rustc_version::Result<rustc_version::Version>> = Lazy
 /// let day = COMPILE_DATE.day();
 /// println!("Compiled on {month} {day}, {year}.");
 /// ```
+#[cfg(feature = "time-types")]
 #[proc_macro]
 pub fn date(_item: TokenStream) -> TokenStream {
-  let date = COMPILE_TIME.date();
-
-  let year = date.year();
-  let month = format_ident!("{}", format!("{:?}", date.month()));
-  let day = date.day();
+  let compile_time = try_compile!(get_compile_time());
+  let date = compile_time.date();
 
-  quote! {
-    match ::time::Date::from_calendar_date(#year, ::time::Month::#month, #day) {
-      Ok(date) => date,
-      _ => ::core::unreachable!(),
-    }
-  }
-  .into()
+  date_tokens(date).into()
 }
 
 /// Compile date as `&'static str` in `yyyy-MM-dd` format.
@@ -61,6 +426,7 @@ pub fn date(_item: TokenStream) -> TokenStream {
 /// # Example
 ///
 /// ```
+/// # #[cfg(feature = "time-types")] {
 /// const COMPILE_DATE: time::Date = compile_time::date!();
 ///
 /// let year = COMPILE_DATE.year();
@@ -69,10 +435,12 @@ pub fn date(_item: TokenStream) -> TokenStream {
 /// let date_string = format!("{year:04}-{month:02}-{day:02}");
 ///
 /// assert_eq!(compile_time::date_str!(), date_string);
+/// # }
 /// ```
 #[proc_macro]
 pub fn date_str(_item: TokenStream) -> TokenStream {
-  let date = COMPILE_TIME.date();
+  let compile_time = try_compile!(get_compile_time());
+  let date = compile_time.date();
 
   let fmt = format_description!("[year]-[month]-[day]");
   let date_str = date.format(&fmt).unwrap();
@@ -80,8 +448,36 @@ pub fn date_str(_item: TokenStream) -> TokenStream {
   quote! { #date_str }.into()
 }
 
+/// Compile date as `&'static str` in ISO 8601 ordinal `yyyy-DDD` format, e.g. `2024-173`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// const COMPILE_DATE: time::Date = compile_time::date!();
+///
+/// let ordinal_date_string = format!("{:04}-{:03}", COMPILE_DATE.year(), COMPILE_DATE.ordinal());
+/// assert_eq!(compile_time::ordinal_date_str!(), ordinal_date_string);
+/// # }
+/// ```
+#[proc_macro]
+pub fn ordinal_date_str(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let date = compile_time.date();
+
+  let fmt = format_description!("[year]-[ordinal]");
+  let ordinal_date_str = date.format(&fmt).unwrap();
+
+  quote! { #ordinal_date_str }.into()
+}
+
 /// Compile time as `time::Time`.
 ///
+/// The time is truncated to whole seconds by default. Use `precision = millis`,
+/// `precision = micros` or `precision = nanos` to keep sub-second precision.
+/// Use `granularity = minute`, `granularity = hour` or `granularity = day` to truncate it further.
+/// Use `offset = "±hh:mm"` to shift it into a fixed UTC offset.
+///
 /// # Example
 ///
 /// ```
@@ -91,22 +487,33 @@ pub fn date_str(_item: TokenStream) -> TokenStream {
 /// let minute = COMPILE_TIME.minute();
 /// let second = COMPILE_TIME.second();
 /// println!("Compiled at {hour:02}:{minute:02}:{second:02}.");
+/// #
+/// # assert_eq!(COMPILE_TIME.nanosecond(), 0);
+/// ```
+///
+/// ```
+/// const COMPILE_TIME: time::Time = compile_time::time!(precision = nanos);
+///
+/// let nanosecond = (compile_time::unix_nanos!() % 1_000_000_000) as u32;
+/// assert_eq!(COMPILE_TIME.nanosecond(), nanosecond);
+/// assert_eq!(compile_time::time!(precision = millis).millisecond(), COMPILE_TIME.millisecond());
+/// ```
+///
 /// ```
+/// const COMPILE_HOUR: time::Time = compile_time::time!(granularity = hour);
+///
+/// assert_eq!(COMPILE_HOUR.hour(), compile_time::hour!());
+/// assert_eq!((COMPILE_HOUR.minute(), COMPILE_HOUR.second()), (0, 0));
+/// ```
+#[cfg(feature = "time-types")]
 #[proc_macro]
-pub fn time(_item: TokenStream) -> TokenStream {
-  let time = COMPILE_TIME.time();
+pub fn time(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as TimeArgs);
+  let compile_time = try_compile!(get_compile_time());
 
-  let hour = time.hour();
-  let minute = time.minute();
-  let second = time.second();
+  let time = args.apply(compile_time).time();
 
-  quote! {
-    match ::time::Time::from_hms(#hour, #minute, #second) {
-      Ok(time) => time,
-      _ => ::core::unreachable!(),
-    }
-  }
-  .into()
+  time_tokens(time, args.precision).into()
 }
 
 /// Compile time as `&'static str` in `hh:mm:ss` format.
@@ -114,6 +521,7 @@ pub fn time(_item: TokenStream) -> TokenStream {
 /// # Example
 ///
 /// ```
+/// # #[cfg(feature = "time-types")] {
 /// const COMPILE_TIME: time::Time = compile_time::time!();
 ///
 /// let hour = COMPILE_TIME.hour();
@@ -122,10 +530,12 @@ pub fn time(_item: TokenStream) -> TokenStream {
 /// let time_string = format!("{hour:02}:{minute:02}:{second:02}");
 ///
 /// assert_eq!(compile_time::time_str!(), time_string);
+/// # }
 /// ```
 #[proc_macro]
 pub fn time_str(_item: TokenStream) -> TokenStream {
-  let time = COMPILE_TIME.time();
+  let compile_time = try_compile!(get_compile_time());
+  let time = compile_time.time();
 
   let fmt = format_description!("[hour]:[minute]:[second]");
   let time_str = time.format(&fmt).unwrap();
@@ -133,8 +543,37 @@ pub fn time_str(_item: TokenStream) -> TokenStream {
   quote! { #time_str }.into()
 }
 
+/// Compile time as `&'static str` in 12-hour `hh:mm:ss AM/PM` format.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// const COMPILE_TIME: time::Time = compile_time::time!();
+///
+/// let (hour, minute, second) = COMPILE_TIME.as_hms();
+/// let period = if hour < 12 { "AM" } else { "PM" };
+/// let hour = if hour % 12 == 0 { 12 } else { hour % 12 };
+/// let time_string = format!("{hour:02}:{minute:02}:{second:02} {period}");
+///
+/// assert_eq!(compile_time::time_str_12h!(), time_string);
+/// # }
+/// ```
+#[proc_macro]
+pub fn time_str_12h(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let time = compile_time.time();
+
+  let fmt = format_description!("[hour repr:12]:[minute]:[second] [period]");
+  let time_str = time.format(&fmt).unwrap();
+
+  quote! { #time_str }.into()
+}
+
 /// Compile date and time as `time::OffsetDateTime`.
 ///
+/// Accepts the same `precision`, `granularity` and `offset` arguments as [`time!`](time!).
+///
 /// # Example
 ///
 /// ```
@@ -155,155 +594,1506 @@ pub fn time_str(_item: TokenStream) -> TokenStream {
 /// # // Additional sanity check.
 /// # let now = time::OffsetDateTime::now_utc();
 /// # let yesterday = now.saturating_sub(time::Duration::days(1));
+/// # #[cfg(not(feature = "deterministic"))]
 /// # assert!(COMPILE_DATETIME > yesterday);
 /// # assert!(COMPILE_DATETIME < now);
 /// ```
+///
+/// ```
+/// const COMPILE_DATETIME_IST: time::OffsetDateTime = compile_time::datetime!(offset = "+05:30");
+///
+/// assert_eq!(COMPILE_DATETIME_IST, compile_time::datetime!());
+/// assert_eq!(COMPILE_DATETIME_IST.offset().as_hms(), (5, 30, 0));
+/// ```
+#[cfg(feature = "time-types")]
 #[proc_macro]
-pub fn datetime(_item: TokenStream) -> TokenStream {
-  let datetime = *COMPILE_TIME;
-
-  let year = datetime.year();
-  let month = format_ident!("{}", format!("{:?}", datetime.month()));
-  let day = datetime.day();
+pub fn datetime(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as TimeArgs);
+  let compile_time = try_compile!(get_compile_time());
 
-  let hour = datetime.hour();
-  let minute = datetime.minute();
-  let second = datetime.second();
+  let datetime = args.apply(compile_time);
 
-  let date = quote! {
-    match ::time::Date::from_calendar_date(#year, ::time::Month::#month, #day) {
-      Ok(date) => date,
-      _ => ::core::unreachable!(),
-    }
-  };
-
-  let time = quote! {
-    match ::time::Time::from_hms(#hour, #minute, #second) {
-      Ok(time) => time,
-      _ => ::core::unreachable!(),
-    }
-  };
-
-  quote! {
-    ::time::PrimitiveDateTime::new(#date, #time).assume_utc()
-  }
-  .into()
+  datetime_tokens(datetime, args.precision).into()
 }
 
-/// Compile time as `&'static str` in `yyyy-MM-ddThh:mm:ssZ` format.
+/// Compile date and time as `time::PrimitiveDateTime`.
+///
+/// Accepts the same `precision`, `granularity` and `offset` arguments as [`time!`](time!).
 ///
 /// # Example
 ///
 /// ```
-/// const COMPILE_DATE_STRING: &str = compile_time::date_str!();
-/// const COMPILE_TIME_STRING: &str = compile_time::time_str!();
+/// const COMPILE_DATETIME: time::PrimitiveDateTime = compile_time::primitive_datetime!();
 ///
-/// let datetime_string = format!("{COMPILE_DATE_STRING}T{COMPILE_TIME_STRING}Z");
-/// assert_eq!(compile_time::datetime_str!(), datetime_string);
+/// assert_eq!(COMPILE_DATETIME.date(), compile_time::date!());
+/// assert_eq!(COMPILE_DATETIME.time(), compile_time::time!());
+/// assert_eq!(COMPILE_DATETIME.assume_utc(), compile_time::datetime!());
 /// ```
+#[cfg(feature = "time-types")]
 #[proc_macro]
-pub fn datetime_str(_item: TokenStream) -> TokenStream {
-  let datetime = *COMPILE_TIME;
+pub fn primitive_datetime(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as TimeArgs);
+  let compile_time = try_compile!(get_compile_time());
 
-  let fmt = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]Z");
-  let datetime_str = datetime.format(&fmt).unwrap();
+  let datetime = args.apply(compile_time);
 
-  quote! { #datetime_str }.into()
+  let date = date_tokens(datetime.date());
+  let time = time_tokens(datetime.time(), args.precision);
+
+  quote! {
+    ::time::PrimitiveDateTime::new(#date, #time)
+  }
+  .into()
 }
 
-/// Compile date and time as UNIX timestamp in seconds.
+/// Compile date and time as `time::UtcDateTime`.
+///
+/// This requires `time` 0.3.38 or later. Accepts the same `precision` and `granularity` arguments
+/// as [`time!`](time!).
 ///
 /// # Example
 ///
 /// ```
-/// const COMPILE_DATETIME: time::OffsetDateTime = compile_time::datetime!();
+/// const COMPILE_DATETIME: time::UtcDateTime = compile_time::utc_datetime!();
 ///
-/// assert_eq!(compile_time::unix!(), COMPILE_DATETIME.unix_timestamp());
+/// assert_eq!(COMPILE_DATETIME, compile_time::datetime!().to_utc());
 /// ```
+#[cfg(feature = "time-types")]
 #[proc_macro]
-pub fn unix(_item: TokenStream) -> TokenStream {
-  let datetime = *COMPILE_TIME;
+pub fn utc_datetime(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as TimeArgs);
+  let compile_time = try_compile!(get_compile_time());
 
-  let unix_timestamp = proc_macro2::Literal::i64_unsuffixed(datetime.unix_timestamp());
+  if let Err(err) = args.reject_offset() {
+    return err.to_compile_error().into();
+  }
+
+  let datetime = args.apply(compile_time);
+
+  let date = date_tokens(datetime.date());
+  let time = time_tokens(datetime.time(), args.precision);
 
   quote! {
-    #unix_timestamp
+    ::time::UtcDateTime::new(#date, #time)
   }
   .into()
 }
 
-/// Rust compiler version as `semver::Version`.
+/// Compile time as `&'static str` in `yyyy-MM-ddThh:mm:ssZ` format.
+///
+/// Pass `Rfc2822`, `Rfc3339` or `Iso8601` to use one of `time`'s well-known formats instead.
+/// Accepts the same `granularity` and `offset` arguments as `time!`, in which case the
+/// default format ends in `±hh:mm` instead of `Z`.
 ///
 /// # Example
 ///
 /// ```
-/// let rustc_version: semver::Version = compile_time::rustc_version!();
-/// assert_eq!(rustc_version, rustc_version::version().unwrap());
+/// const COMPILE_DATE_STRING: &str = compile_time::date_str!();
+/// const COMPILE_TIME_STRING: &str = compile_time::time_str!();
+///
+/// let datetime_string = format!("{COMPILE_DATE_STRING}T{COMPILE_TIME_STRING}Z");
+/// assert_eq!(compile_time::datetime_str!(), datetime_string);
 /// ```
-#[proc_macro]
-pub fn rustc_version(_item: TokenStream) -> TokenStream {
-  let rustc_version::Version { major, minor, patch, pre, build } = match &*RUSTC_VERSION {
-    Ok(rustc_version) => rustc_version,
-    Err(err) => panic!("Failed to get version: {}", err),
-  };
+///
+/// ```
+/// use time::format_description::well_known::{Iso8601, Rfc2822, Rfc3339};
+///
+/// # #[cfg(feature = "time-types")]
+/// const COMPILE_DATETIME: time::OffsetDateTime = compile_time::datetime!();
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(compile_time::datetime_str!(Rfc2822), COMPILE_DATETIME.format(&Rfc2822).unwrap());
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(compile_time::datetime_str!(Rfc3339), COMPILE_DATETIME.format(&Rfc3339).unwrap());
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(compile_time::datetime_str!(Iso8601), COMPILE_DATETIME.format(&Iso8601::DEFAULT).unwrap());
+///
+/// # #[cfg(feature = "time-types")]
+/// const COMPILE_DAY: time::OffsetDateTime = compile_time::datetime!(granularity = day);
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(compile_time::datetime_str!(Rfc3339, granularity = day), COMPILE_DAY.format(&Rfc3339).unwrap());
+/// assert_eq!(compile_time::datetime_str!(granularity = day), format!("{}T00:00:00Z", compile_time::date_str!()));
+///
+/// # #[cfg(feature = "time-types")]
+/// const COMPILE_DATETIME_IST: time::OffsetDateTime = compile_time::datetime!(offset = "+05:30");
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(compile_time::datetime_str!(Rfc3339, offset = "+05:30"), COMPILE_DATETIME_IST.format(&Rfc3339).unwrap());
+/// assert!(compile_time::datetime_str!(offset = "+05:30").ends_with("+05:30"));
+/// ```
+#[proc_macro]
+pub fn datetime_str(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as DatetimeStrArgs);
+  let compile_time = try_compile!(get_compile_time());
+
+  let datetime_str = try_compile!(format_datetime(args.apply(compile_time), args.format));
+
+  quote! { #datetime_str }.into()
+}
+
+/// Compile date and time as `&'static str` in `yyyy-MM-dd hh:mm:ss` format, without offset.
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_DATE_STRING: &str = compile_time::date_str!();
+/// const COMPILE_TIME_STRING: &str = compile_time::time_str!();
+///
+/// let naive_datetime_string = format!("{COMPILE_DATE_STRING} {COMPILE_TIME_STRING}");
+/// assert_eq!(compile_time::naive_datetime_str!(), naive_datetime_string);
+/// ```
+#[proc_macro]
+pub fn naive_datetime_str(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+
+  let fmt = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+  let naive_datetime_str = datetime.format(&fmt).unwrap();
+
+  quote! { #naive_datetime_str }.into()
+}
+
+/// Compile date and time as `&'static str` in RFC 2822 format, e.g. `Tue, 01 Jul 2003 10:52:37 +0000`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// use time::format_description::well_known::Rfc2822;
+///
+/// const COMPILE_DATETIME: time::OffsetDateTime = compile_time::datetime!();
+///
+/// assert_eq!(compile_time::rfc2822_str!(), COMPILE_DATETIME.format(&Rfc2822).unwrap());
+/// # }
+/// ```
+#[proc_macro]
+pub fn rfc2822_str(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let rfc2822_str = try_compile!(format_datetime(compile_time, Some(WellKnownFormat::Rfc2822)));
+
+  quote! { #rfc2822_str }.into()
+}
+
+/// Current date and time at the macro invocation as `time::OffsetDateTime`.
+///
+/// Unlike all other macros, which share a single compile time, this evaluates the current time
+/// separately for every invocation. Accepts the same arguments as [`time!`](time!).
+///
+/// With the `deterministic` feature, this is the fixed compile time instead.
+///
+/// # Example
+///
+/// ```
+/// const FIRST: time::OffsetDateTime = compile_time::now!(precision = nanos);
+/// const SECOND: time::OffsetDateTime = compile_time::now!(precision = nanos);
+///
+/// # #[cfg(not(feature = "deterministic"))]
+/// assert!(FIRST < SECOND);
+/// assert!(compile_time::datetime!() <= FIRST);
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn now(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as TimeArgs);
+
+  let now = try_compile!(capture_now());
+  let datetime = args.apply(now);
+
+  datetime_tokens(datetime, args.precision).into()
+}
+
+/// Current date and time at the macro invocation as `&'static str`.
+///
+/// Unlike all other macros, which share a single compile time, this evaluates the current time
+/// separately for every invocation. Accepts the same arguments as [`datetime_str!`](datetime_str!).
+///
+/// With the `deterministic` feature, this is the fixed compile time instead.
+///
+/// # Example
+///
+/// ```
+/// const NOW_STRING: &str = compile_time::now_str!(Rfc3339);
+///
+/// assert!(compile_time::datetime_str!(Rfc3339) <= NOW_STRING);
+/// ```
+#[proc_macro]
+pub fn now_str(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as DatetimeStrArgs);
+
+  let now = try_compile!(capture_now());
+  let now_str = try_compile!(format_datetime(args.apply(now), args.format));
+
+  quote! { #now_str }.into()
+}
+
+/// Compile date and time in the build machine's local UTC offset as `time::OffsetDateTime`.
+///
+/// Accepts the same `precision` and `granularity` arguments as [`time!`](time!).
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_DATETIME_LOCAL: time::OffsetDateTime = compile_time::datetime_local!();
+///
+/// let offset = COMPILE_DATETIME_LOCAL.offset();
+/// println!("Compiled at {COMPILE_DATETIME_LOCAL} (UTC offset {offset}).");
+/// #
+/// # assert_eq!(COMPILE_DATETIME_LOCAL, compile_time::datetime!());
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn datetime_local(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as TimeArgs);
+  let compile_time = try_compile!(get_compile_time());
+  let local_offset = try_compile!(local_offset());
+
+  if let Err(err) = args.reject_offset() {
+    return err.to_compile_error().into();
+  }
+
+  let datetime = args.apply(compile_time.to_offset(local_offset));
+
+  datetime_tokens(datetime, args.precision).into()
+}
+
+/// Compile date and time in the build machine's local UTC offset as `&'static str`
+/// in `yyyy-MM-ddThh:mm:ss±hh:mm` format.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// const COMPILE_DATETIME_LOCAL: time::OffsetDateTime = compile_time::datetime_local!();
+///
+/// let date = COMPILE_DATETIME_LOCAL.date();
+/// let (hour, minute, second) = COMPILE_DATETIME_LOCAL.to_hms();
+/// let (offset_hours, offset_minutes, _) = COMPILE_DATETIME_LOCAL.offset().as_hms();
+/// let datetime_string =
+///   format!("{date}T{hour:02}:{minute:02}:{second:02}{offset_hours:+03}:{:02}", offset_minutes.abs());
+///
+/// assert_eq!(compile_time::datetime_local_str!(), datetime_string);
+/// # }
+/// ```
+#[proc_macro]
+pub fn datetime_local_str(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let local_offset = try_compile!(local_offset());
+  let datetime = compile_time.to_offset(local_offset);
+
+  let datetime_local_str = try_compile!(format_offset_datetime(datetime));
+
+  quote! { #datetime_local_str }.into()
+}
+
+/// Compile date and time in the given IANA time zone as `time::OffsetDateTime`.
+///
+/// The time zone is looked up in a bundled copy of the IANA time zone database.
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_DATETIME_VIENNA: time::OffsetDateTime = compile_time::datetime_in_tz!("Europe/Vienna");
+///
+/// assert_eq!(COMPILE_DATETIME_VIENNA, compile_time::datetime!());
+/// assert!([1, 2].contains(&COMPILE_DATETIME_VIENNA.offset().whole_hours()));
+/// ```
+#[cfg(all(feature = "time-types", feature = "tz"))]
+#[proc_macro]
+pub fn datetime_in_tz(item: TokenStream) -> TokenStream {
+  let tz = parse_macro_input!(item as syn::LitStr);
+  let compile_time = try_compile!(get_compile_time());
+
+  let offset = match tz_offset(&tz) {
+    Ok(offset) => offset,
+    Err(err) => return err.to_compile_error().into(),
+  };
+
+  datetime_tokens(compile_time.to_offset(offset), Precision::Seconds).into()
+}
+
+/// Compile date and time in the given IANA time zone as `&'static str` in `yyyy-MM-ddThh:mm:ss±hh:mm` format.
+///
+/// The time zone is looked up in a bundled copy of the IANA time zone database.
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_DATETIME_STRING: &str = compile_time::datetime_in_tz_str!("Asia/Kolkata");
+///
+/// assert!(COMPILE_DATETIME_STRING.ends_with("+05:30"));
+/// ```
+#[cfg(feature = "tz")]
+#[proc_macro]
+pub fn datetime_in_tz_str(item: TokenStream) -> TokenStream {
+  let tz = parse_macro_input!(item as syn::LitStr);
+  let compile_time = try_compile!(get_compile_time());
+
+  let offset = match tz_offset(&tz) {
+    Ok(offset) => offset,
+    Err(err) => return err.to_compile_error().into(),
+  };
+
+  let datetime_in_tz_str = try_compile!(format_offset_datetime(compile_time.to_offset(offset)));
+
+  quote! { #datetime_in_tz_str }.into()
+}
+
+/// Build machine's local UTC offset at compile time as `time::UtcOffset`.
+///
+/// # Example
+///
+/// ```
+/// const UTC_OFFSET: time::UtcOffset = compile_time::utc_offset!();
+///
+/// assert_eq!(UTC_OFFSET, compile_time::datetime_local!().offset());
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn utc_offset(_item: TokenStream) -> TokenStream {
+  let local_offset = try_compile!(local_offset());
+  offset_tokens(local_offset).into()
+}
+
+/// Build machine's local UTC offset at compile time as `&'static str` in `±hh:mm` format.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")]
+/// const UTC_OFFSET: time::UtcOffset = compile_time::utc_offset!();
+///
+/// # #[cfg(feature = "time-types")]
+/// let (hours, minutes, _) = UTC_OFFSET.as_hms();
+/// # #[cfg(feature = "time-types")]
+/// let utc_offset_string = format!("{hours:+03}:{:02}", minutes.abs());
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(compile_time::utc_offset_str!(), utc_offset_string);
+/// assert!(compile_time::datetime_local_str!().ends_with(compile_time::utc_offset_str!()));
+/// ```
+#[proc_macro]
+pub fn utc_offset_str(_item: TokenStream) -> TokenStream {
+  let offset = try_compile!(local_offset());
+
+  let fmt = format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+  let utc_offset_str = offset.format(&fmt).unwrap();
+
+  quote! { #utc_offset_str }.into()
+}
+
+/// Compile year as integer literal.
+///
+/// # Example
+///
+/// ```
+/// let year = compile_time::year!();
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(year, compile_time::date!().year());
+/// ```
+#[proc_macro]
+pub fn year(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let year = compile_time.year();
+
+  proc_macro2::Literal::i32_unsuffixed(year).to_token_stream().into()
+}
+
+/// Compile month (1–12) as integer literal.
+///
+/// # Example
+///
+/// ```
+/// let month = compile_time::month!();
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(month, u8::from(compile_time::date!().month()));
+/// assert!((1..=12).contains(&month));
+/// ```
+#[proc_macro]
+pub fn month(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let month = compile_time.month() as u8;
+
+  proc_macro2::Literal::u8_unsuffixed(month).to_token_stream().into()
+}
+
+/// Compile day of the month as integer literal.
+///
+/// # Example
+///
+/// ```
+/// const DAYS: [(); compile_time::day!()] = [(); compile_time::day!()];
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(DAYS.len(), compile_time::date!().day() as usize);
+/// ```
+#[proc_macro]
+pub fn day(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let day = compile_time.day();
+
+  proc_macro2::Literal::u8_unsuffixed(day).to_token_stream().into()
+}
+
+/// Compile hour as integer literal.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// assert_eq!(compile_time::hour!(), compile_time::time!().hour());
+/// # }
+/// ```
+#[proc_macro]
+pub fn hour(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let hour = compile_time.hour();
+
+  proc_macro2::Literal::u8_unsuffixed(hour).to_token_stream().into()
+}
+
+/// Compile minute as integer literal.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// assert_eq!(compile_time::minute!(), compile_time::time!().minute());
+/// # }
+/// ```
+#[proc_macro]
+pub fn minute(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let minute = compile_time.minute();
+
+  proc_macro2::Literal::u8_unsuffixed(minute).to_token_stream().into()
+}
+
+/// Compile second as integer literal.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// assert_eq!(compile_time::second!(), compile_time::time!().second());
+/// # }
+/// ```
+#[proc_macro]
+pub fn second(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let second = compile_time.second();
+
+  proc_macro2::Literal::u8_unsuffixed(second).to_token_stream().into()
+}
+
+/// Compile date as tuple of integer literals `(year, month, day)`, e.g. `(2024u16, 6u8, 21u8)`.
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_DATE: (u16, u8, u8) = compile_time::date_tuple!();
+///
+/// assert_eq!(COMPILE_DATE, (compile_time::year!(), compile_time::month!(), compile_time::day!()));
+/// ```
+#[proc_macro]
+pub fn date_tuple(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let year = proc_macro2::Literal::u16_suffixed(try_compile!(tuple_year(compile_time)));
+  let month = proc_macro2::Literal::u8_suffixed(compile_time.month() as u8);
+  let day = proc_macro2::Literal::u8_suffixed(compile_time.day());
+
+  quote! { (#year, #month, #day) }.into()
+}
+
+/// Compile date and time as tuple of integer literals `(year, month, day, hour, minute, second)`,
+/// e.g. `(2024u16, 6u8, 21u8, 15u8, 42u8, 33u8)`.
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_DATETIME: (u16, u8, u8, u8, u8, u8) = compile_time::datetime_tuple!();
+///
+/// let (year, month, day, hour, minute, second) = COMPILE_DATETIME;
+/// assert_eq!((year, month, day), compile_time::date_tuple!());
+/// assert_eq!((hour, minute, second), (compile_time::hour!(), compile_time::minute!(), compile_time::second!()));
+/// ```
+#[proc_macro]
+pub fn datetime_tuple(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let year = proc_macro2::Literal::u16_suffixed(try_compile!(tuple_year(compile_time)));
+  let month = proc_macro2::Literal::u8_suffixed(compile_time.month() as u8);
+  let day = proc_macro2::Literal::u8_suffixed(compile_time.day());
+  let hour = proc_macro2::Literal::u8_suffixed(compile_time.hour());
+  let minute = proc_macro2::Literal::u8_suffixed(compile_time.minute());
+  let second = proc_macro2::Literal::u8_suffixed(compile_time.second());
+
+  quote! { (#year, #month, #day, #hour, #minute, #second) }.into()
+}
+
+/// Compile date as `u32` literal in `yyyyMMdd` format, e.g. `20240621`.
+///
+/// # Example
+///
+/// ```
+/// let date_slug: u32 = compile_time::date_slug!();
+///
+/// assert_eq!(date_slug.to_string(), compile_time::date_str!().replace('-', ""));
+/// ```
+#[proc_macro]
+pub fn date_slug(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let year = try_compile!(slug_year(compile_time));
+  let date_slug = year as u32 * 10_000 + compile_time.month() as u32 * 100 + compile_time.day() as u32;
+
+  proc_macro2::Literal::u32_suffixed(date_slug).to_token_stream().into()
+}
+
+/// Compile date and time as `u64` literal in `yyyyMMddhhmmss` format, e.g. `20240621154233`.
+///
+/// # Example
+///
+/// ```
+/// let datetime_slug: u64 = compile_time::datetime_slug!();
+///
+/// assert_eq!(datetime_slug / 1_000_000, compile_time::date_slug!() as u64);
+/// assert_eq!(datetime_slug.to_string(), compile_time::naive_datetime_str!().replace(['-', ' ', ':'], ""));
+/// ```
+#[proc_macro]
+pub fn datetime_slug(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let year = try_compile!(slug_year(compile_time));
+  let date_slug = year * 10_000 + compile_time.month() as u64 * 100 + compile_time.day() as u64;
+  let time_slug =
+    compile_time.hour() as u64 * 10_000 + compile_time.minute() as u64 * 100 + compile_time.second() as u64;
+
+  proc_macro2::Literal::u64_suffixed(date_slug * 1_000_000 + time_slug).to_token_stream().into()
+}
+
+/// Compile day of the year (1–366) as integer literal.
+///
+/// # Example
+///
+/// ```
+/// let day_of_year = compile_time::day_of_year!();
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(day_of_year, compile_time::date!().ordinal());
+/// assert!((1..=366).contains(&day_of_year));
+/// ```
+#[proc_macro]
+pub fn day_of_year(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let day_of_year = compile_time.ordinal();
+
+  proc_macro2::Literal::u16_unsuffixed(day_of_year).to_token_stream().into()
+}
+
+/// Compile ISO 8601 week number (1–53) as integer literal.
+///
+/// # Example
+///
+/// ```
+/// let iso_week = compile_time::iso_week!();
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(iso_week, compile_time::date!().iso_week());
+/// assert!((1..=53).contains(&iso_week));
+/// ```
+#[proc_macro]
+pub fn iso_week(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let iso_week = compile_time.iso_week();
+
+  proc_macro2::Literal::u8_unsuffixed(iso_week).to_token_stream().into()
+}
+
+/// Compile ISO 8601 week-based year as integer literal.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// let (iso_year, iso_week, _) = compile_time::date!().to_iso_week_date();
+///
+/// assert_eq!(compile_time::iso_year!(), iso_year);
+/// println!("Compiled in week {iso_week} of {iso_year}.");
+/// # }
+/// ```
+#[proc_macro]
+pub fn iso_year(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let (iso_year, _, _) = compile_time.to_iso_week_date();
+
+  proc_macro2::Literal::i32_unsuffixed(iso_year).to_token_stream().into()
+}
+
+/// Compile day of the week as `time::Weekday`.
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_WEEKDAY: time::Weekday = compile_time::weekday!();
+///
+/// assert_eq!(COMPILE_WEEKDAY, compile_time::date!().weekday());
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn weekday(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let weekday = format_ident!("{}", format!("{:?}", compile_time.weekday()));
+
+  quote! { ::time::Weekday::#weekday }.into()
+}
+
+/// Compile day of the week as `&'static str`, e.g. `Tuesday`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// const COMPILE_WEEKDAY: time::Weekday = compile_time::weekday!();
+///
+/// assert_eq!(compile_time::weekday_str!(), COMPILE_WEEKDAY.to_string());
+/// # }
+/// ```
+#[proc_macro]
+pub fn weekday_str(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let weekday_str = compile_time.weekday().to_string();
+
+  quote! { #weekday_str }.into()
+}
+
+/// Compile month name as `&'static str`, e.g. `January`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// const COMPILE_DATE: time::Date = compile_time::date!();
+///
+/// assert_eq!(compile_time::month_name_str!(), COMPILE_DATE.month().to_string());
+/// # }
+/// ```
+#[proc_macro]
+pub fn month_name_str(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let month_name_str = compile_time.month().to_string();
+
+  quote! { #month_name_str }.into()
+}
+
+/// Compile day of the week name as `&'static str`, e.g. `Tuesday`.
+///
+/// This is the same as [`weekday_str!`](weekday_str!).
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(compile_time::weekday_name_str!(), compile_time::weekday_str!());
+/// ```
+#[proc_macro]
+pub fn weekday_name_str(item: TokenStream) -> TokenStream {
+  weekday_str(item)
+}
+
+/// Compile date and time as UNIX timestamp in seconds.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// const COMPILE_DATETIME: time::OffsetDateTime = compile_time::datetime!();
+///
+/// assert_eq!(compile_time::unix!(), COMPILE_DATETIME.unix_timestamp());
+/// # }
+/// ```
+#[proc_macro]
+pub fn unix(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+
+  let unix_timestamp = proc_macro2::Literal::i64_unsuffixed(datetime.unix_timestamp());
+
+  quote! {
+    #unix_timestamp
+  }
+  .into()
+}
+
+/// Compile date and time as UNIX timestamp in microseconds.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")] {
+/// const COMPILE_DATETIME: time::OffsetDateTime = compile_time::datetime!();
+///
+/// assert_eq!(compile_time::unix_micros!() / 1_000_000, COMPILE_DATETIME.unix_timestamp());
+/// # }
+/// ```
+#[proc_macro]
+pub fn unix_micros(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+
+  let unix_timestamp_micros = (datetime.unix_timestamp_nanos() / 1_000) as i64;
+  let unix_timestamp_micros = proc_macro2::Literal::i64_unsuffixed(unix_timestamp_micros);
+
+  quote! {
+    #unix_timestamp_micros
+  }
+  .into()
+}
+
+/// Compile date and time as UNIX timestamp in nanoseconds.
+///
+/// The timestamp is an `i128` by default. Pass `u64` to get a `u64` instead.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")]
+/// const COMPILE_DATETIME: time::OffsetDateTime = compile_time::datetime!();
+///
+/// let unix_nanos: i128 = compile_time::unix_nanos!();
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(unix_nanos / 1_000_000_000, COMPILE_DATETIME.unix_timestamp() as i128);
+///
+/// let unix_nanos_u64: u64 = compile_time::unix_nanos!(u64);
+/// assert_eq!(unix_nanos_u64 as i128, unix_nanos);
+/// ```
+#[proc_macro]
+pub fn unix_nanos(item: TokenStream) -> TokenStream {
+  let ty = parse_macro_input!(item as Option<Ident>);
+  let datetime = try_compile!(get_compile_time());
+
+  let unix_timestamp_nanos = datetime.unix_timestamp_nanos();
+
+  let unix_timestamp_nanos = match ty {
+    None => proc_macro2::Literal::i128_suffixed(unix_timestamp_nanos),
+    Some(ty) if ty == "i128" => proc_macro2::Literal::i128_suffixed(unix_timestamp_nanos),
+    Some(ty) if ty == "u64" => match u64::try_from(unix_timestamp_nanos) {
+      Ok(unix_timestamp_nanos) => proc_macro2::Literal::u64_suffixed(unix_timestamp_nanos),
+      Err(_) => return syn::Error::new(ty.span(), "timestamp does not fit into `u64`").to_compile_error().into(),
+    },
+    Some(ty) => return syn::Error::new(ty.span(), "expected `i128` or `u64`").to_compile_error().into(),
+  };
+
+  quote! {
+    #unix_timestamp_nanos
+  }
+  .into()
+}
+
+/// Cache-busting token as `&'static str`, e.g. for appending `?v=...` to asset URLs.
+///
+/// The token is the compile time as UNIX timestamp in nanoseconds in lowercase hexadecimal, so it
+/// changes whenever the compile time does.
+///
+/// # Example
+///
+/// ```
+/// const CACHE_BUST_TOKEN: &str = compile_time::cache_bust_token!();
+///
+/// assert_eq!(CACHE_BUST_TOKEN, format!("{:x}", compile_time::unix_nanos!()));
+///
+/// let url = format!("/static/app.js?v={CACHE_BUST_TOKEN}");
+/// ```
+#[proc_macro]
+pub fn cache_bust_token(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+
+  let cache_bust_token = format!("{:x}", datetime.unix_timestamp_nanos());
+
+  quote! {
+    #cache_bust_token
+  }
+  .into()
+}
+
+/// Compile date and time as UNIX timestamp in fractional seconds.
+///
+/// # Example
+///
+/// ```
+/// let unix_f64: f64 = compile_time::unix_f64!();
+///
+/// assert_eq!(unix_f64.trunc() as i64, compile_time::unix!());
+/// ```
+#[proc_macro]
+pub fn unix_f64(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+
+  let unix_timestamp = datetime.unix_timestamp() as f64 + datetime.nanosecond() as f64 / 1_000_000_000.0;
+  let unix_timestamp = proc_macro2::Literal::f64_unsuffixed(unix_timestamp);
+
+  quote! {
+    #unix_timestamp
+  }
+  .into()
+}
+
+/// Compile date and time as Windows `FILETIME`, i.e. 100-nanosecond intervals since 1601-01-01.
+///
+/// # Example
+///
+/// ```
+/// const FILETIME_UNIX_EPOCH: u64 = 116_444_736_000_000_000;
+///
+/// let filetime: u64 = compile_time::filetime!();
+/// assert_eq!((filetime - FILETIME_UNIX_EPOCH) / 10_000_000, compile_time::unix!());
+/// ```
+#[proc_macro]
+pub fn filetime(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+  const FILETIME_UNIX_EPOCH: i128 = 116_444_736_000_000_000;
+
+  let Ok(filetime) = u64::try_from(datetime.unix_timestamp_nanos().div_euclid(100) + FILETIME_UNIX_EPOCH) else {
+    return syn::Error::new(proc_macro2::Span::call_site(), "compile time is out of range for the `FILETIME` format")
+      .to_compile_error()
+      .into();
+  };
+  let filetime = proc_macro2::Literal::u64_suffixed(filetime);
+
+  quote! {
+    #filetime
+  }
+  .into()
+}
+
+/// Compile date and time (UTC) in the 32-bit DOS/FAT format used e.g. in ZIP file headers.
+///
+/// The date is stored in the upper and the time in the lower 16 bits. Seconds are stored
+/// with a resolution of two seconds.
+///
+/// # Example
+///
+/// ```
+/// # // The UNIX epoch used by the `deterministic` feature is before the DOS epoch.
+/// # #[cfg(not(feature = "deterministic"))] {
+/// let dos_datetime: u32 = compile_time::dos_datetime!();
+///
+/// let (date, time) = ((dos_datetime >> 16) as u16, dos_datetime as u16);
+/// assert_eq!((date >> 9) as i32 + 1980, compile_time::year!());
+/// assert_eq!((date >> 5 & 0xf) as u8, compile_time::month!());
+/// assert_eq!((date & 0x1f) as u8, compile_time::day!());
+/// assert_eq!((time >> 11) as u8, compile_time::hour!());
+/// assert_eq!((time >> 5 & 0x3f) as u8, compile_time::minute!());
+/// assert_eq!((time & 0x1f) as u8 * 2, compile_time::second!() / 2 * 2);
+/// # }
+/// ```
+#[proc_macro]
+pub fn dos_datetime(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+
+  let year = datetime.year() - 1980;
+  if !(0..128).contains(&year) {
+    return syn::Error::new(proc_macro2::Span::call_site(), "compile time is out of range for the DOS date format")
+      .to_compile_error()
+      .into();
+  }
+
+  let date = (year as u32) << 9 | (datetime.month() as u32) << 5 | datetime.day() as u32;
+  let time = (datetime.hour() as u32) << 11 | (datetime.minute() as u32) << 5 | (datetime.second() as u32 / 2);
+
+  let dos_datetime = proc_macro2::Literal::u32_suffixed(date << 16 | time);
+
+  quote! {
+    #dos_datetime
+  }
+  .into()
+}
+
+/// Compile date and time as seconds since the given epoch.
+///
+/// The epoch is given either in `yyyy-MM-dd` or in RFC 3339 format.
+///
+/// # Example
+///
+/// ```
+/// const Y2K_EPOCH: i64 = 946_684_800;
+///
+/// assert_eq!(compile_time::seconds_since!("2000-01-01T00:00:00Z"), compile_time::unix!() - Y2K_EPOCH);
+/// assert_eq!(compile_time::seconds_since!("1970-01-01"), compile_time::unix!());
+/// ```
+#[proc_macro]
+pub fn seconds_since(item: TokenStream) -> TokenStream {
+  let epoch = parse_macro_input!(item as DatetimeLit);
+  let compile_time = try_compile!(get_compile_time());
+
+  let seconds_since = (compile_time - epoch.datetime).whole_seconds();
+  let seconds_since = proc_macro2::Literal::i64_unsuffixed(seconds_since);
+
+  quote! {
+    #seconds_since
+  }
+  .into()
+}
+
+/// Number of whole weeks between the given epoch and the compile date and time as integer literal.
+///
+/// The epoch is given either in `yyyy-MM-dd` or in RFC 3339 format and defaults to the UNIX epoch.
+///
+/// # Example
+///
+/// ```
+/// let cohort = compile_time::build_epoch_week!("2024-01-01");
+///
+/// assert_eq!(cohort, compile_time::seconds_since!("2024-01-01") / (7 * 24 * 60 * 60));
+/// assert_eq!(compile_time::build_epoch_week!(), compile_time::unix!() / (7 * 24 * 60 * 60));
+/// ```
+#[proc_macro]
+pub fn build_epoch_week(item: TokenStream) -> TokenStream {
+  let EpochArgs { epoch } = parse_macro_input!(item as EpochArgs);
+  let compile_time = try_compile!(get_compile_time());
 
-  let pre = if pre.is_empty() {
-    quote! { ::semver::Prerelease::EMPTY }
+  let build_epoch_week = (compile_time - epoch).whole_weeks();
+  let build_epoch_week = proc_macro2::Literal::i64_unsuffixed(build_epoch_week);
+
+  quote! {
+    #build_epoch_week
+  }
+  .into()
+}
+
+/// Number of days from the compile date until the given date as signed integer literal.
+///
+/// The date is given either in `yyyy-MM-dd` or in RFC 3339 format, and is compared in UTC.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")]
+/// const COMPILE_DATE: time::Date = compile_time::date!();
+///
+/// let days_until = compile_time::days_until!("2100-01-01");
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(days_until, (time::macros::date!(2100-01-01) - COMPILE_DATE).whole_days());
+/// assert!(compile_time::days_until!("1970-01-01") <= 0);
+/// ```
+#[proc_macro]
+pub fn days_until(item: TokenStream) -> TokenStream {
+  let deadline = parse_macro_input!(item as DatetimeLit);
+  let compile_time = try_compile!(get_compile_time());
+
+  let days_until = (deadline.datetime.to_offset(UtcOffset::UTC).date() - compile_time.date()).whole_days();
+  let days_until = proc_macro2::Literal::i64_unsuffixed(days_until);
+
+  quote! {
+    #days_until
+  }
+  .into()
+}
+
+/// Number of seconds from the compile date and time until the given date or date and time as
+/// signed integer literal.
+///
+/// The deadline is given either in `yyyy-MM-dd` or in RFC 3339 format.
+///
+/// # Example
+///
+/// ```
+/// const Y2K38: i64 = 2_147_483_648;
+///
+/// assert_eq!(compile_time::seconds_until!("2038-01-19T03:14:08Z"), Y2K38 - compile_time::unix!());
+/// assert_eq!(compile_time::seconds_until!("2000-01-01"), -compile_time::seconds_since!("2000-01-01"));
+/// ```
+#[proc_macro]
+pub fn seconds_until(item: TokenStream) -> TokenStream {
+  let deadline = parse_macro_input!(item as DatetimeLit);
+  let compile_time = try_compile!(get_compile_time());
+
+  let seconds_until = deadline.datetime.unix_timestamp() - compile_time.unix_timestamp();
+  let seconds_until = proc_macro2::Literal::i64_unsuffixed(seconds_until);
+
+  quote! {
+    #seconds_until
+  }
+  .into()
+}
+
+/// Time between the given date or date and time and the compile time as `time::Duration`.
+///
+/// The epoch is given either in `yyyy-MM-dd` or in RFC 3339 format. Pass `core` as second argument
+/// to get a `core::time::Duration` instead, in which case the epoch must not be after the compile time.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "time-types")]
+/// const SINCE_1970: time::Duration = compile_time::duration_since!("1970-01-01");
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(SINCE_1970.whole_seconds(), compile_time::seconds_since!("1970-01-01"));
+///
+/// const SINCE_1970_CORE: core::time::Duration = compile_time::duration_since!("1970-01-01", core);
+///
+/// # #[cfg(feature = "time-types")]
+/// assert_eq!(SINCE_1970_CORE.as_secs() as i64, SINCE_1970.whole_seconds());
+/// ```
+#[proc_macro]
+pub fn duration_since(item: TokenStream) -> TokenStream {
+  let DurationSinceArgs { epoch, core } = parse_macro_input!(item as DurationSinceArgs);
+  let compile_time = try_compile!(get_compile_time());
+
+  let seconds = compile_time.unix_timestamp() - epoch.datetime.unix_timestamp();
+
+  if core {
+    let Ok(seconds) = u64::try_from(seconds) else {
+      return syn::Error::new(epoch.lit.span(), "epoch is after the compile time").to_compile_error().into();
+    };
+
+    quote! { ::core::time::Duration::from_secs(#seconds) }.into()
+  } else if cfg!(feature = "time-types") {
+    quote! { ::time::Duration::seconds(#seconds) }.into()
   } else {
-    let pre = pre.as_str();
-    quote! {
-      if let Ok(pre) = ::semver::Prerelease::new(#pre) {
-        pre
-      } else {
-        ::core::unreachable!()
-      }
-    }
+    syn::Error::new(
+      epoch.lit.span(),
+      "emitting a `time::Duration` requires the `time-types` feature, pass `core` instead",
+    )
+    .to_compile_error()
+    .into()
+  }
+}
+
+/// Compile date and time as UNIX timestamp in seconds as big-endian `[u8; 8]` array.
+///
+/// Pass `le` to get the little-endian representation instead.
+///
+/// # Example
+///
+/// ```
+/// const COMPILE_DATETIME_BYTES: [u8; 8] = compile_time::datetime_bytes!();
+///
+/// assert_eq!(i64::from_be_bytes(COMPILE_DATETIME_BYTES), compile_time::unix!());
+/// assert_eq!(i64::from_le_bytes(compile_time::datetime_bytes!(le)), compile_time::unix!());
+/// ```
+#[proc_macro]
+pub fn datetime_bytes(item: TokenStream) -> TokenStream {
+  let endianness = parse_macro_input!(item as Option<Ident>);
+  let compile_time = try_compile!(get_compile_time());
+
+  let unix_timestamp = compile_time.unix_timestamp();
+
+  let bytes = match endianness {
+    None => unix_timestamp.to_be_bytes(),
+    Some(endianness) if endianness == "be" => unix_timestamp.to_be_bytes(),
+    Some(endianness) if endianness == "le" => unix_timestamp.to_le_bytes(),
+    Some(endianness) => return syn::Error::new(endianness.span(), "expected `be` or `le`").to_compile_error().into(),
   };
 
-  let build = if build.is_empty() {
-    quote! { ::semver::BuildMetadata::EMPTY }
+  quote! { [#(#bytes),*] }.into()
+}
+
+/// Compile date and time as `std::time::SystemTime`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::{SystemTime, UNIX_EPOCH};
+///
+/// let compile_time: SystemTime = compile_time::system_time!();
+///
+/// let unix_timestamp = compile_time.duration_since(UNIX_EPOCH).unwrap().as_secs();
+/// assert_eq!(unix_timestamp, compile_time::unix!());
+/// ```
+#[proc_macro]
+pub fn system_time(_item: TokenStream) -> TokenStream {
+  let datetime = try_compile!(get_compile_time());
+
+  let unix_timestamp = datetime.unix_timestamp();
+  let seconds = unix_timestamp.unsigned_abs();
+
+  if unix_timestamp < 0 {
+    quote! {
+      ::std::time::UNIX_EPOCH - ::core::time::Duration::from_secs(#seconds)
+    }
   } else {
-    let build = build.as_str();
     quote! {
-      if let Ok(build) = ::semver::BuildMetadata::new(#build) {
-        build
+      ::std::time::UNIX_EPOCH + ::core::time::Duration::from_secs(#seconds)
+    }
+  }
+  .into()
+}
+
+/// Time elapsed since compilation as `time::Duration`.
+///
+/// This expands to an expression which is evaluated at runtime.
+///
+/// # Example
+///
+/// ```
+/// let elapsed: time::Duration = compile_time::elapsed_since_compile!();
+///
+/// assert!(elapsed.is_positive());
+/// println!("Built {} hours ago.", elapsed.whole_hours());
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn elapsed_since_compile(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let datetime = datetime_tokens(compile_time, Precision::Seconds);
+
+  quote! {
+    ::time::OffsetDateTime::now_utc() - #datetime
+  }
+  .into()
+}
+
+/// Time elapsed since compilation as humanized `String`, e.g. `3 days ago`.
+///
+/// This expands to an expression which is evaluated at runtime.
+///
+/// # Example
+///
+/// ```
+/// let build_age: String = compile_time::build_age_str!();
+///
+/// assert!(build_age.ends_with(" ago"));
+/// println!("Built {build_age}.");
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn build_age_str(_item: TokenStream) -> TokenStream {
+  let elapsed = elapsed_since_compile(TokenStream::new());
+  let elapsed = proc_macro2::TokenStream::from(elapsed);
+
+  quote! {
+    {
+      let seconds = (#elapsed).whole_seconds().max(0);
+
+      let (count, unit) = match seconds {
+        0..=59 => (seconds, "second"),
+        60..=3599 => (seconds / 60, "minute"),
+        3600..=86399 => (seconds / 3600, "hour"),
+        _ => (seconds / 86400, "day"),
+      };
+
+      if count == 1 {
+        ::std::format!("{} {} ago", count, unit)
       } else {
-        ::core::unreachable!()
+        ::std::format!("{} {}s ago", count, unit)
       }
     }
+  }
+  .into()
+}
+
+/// Expiry date and time of the build as `time::OffsetDateTime`, i.e. the compile time plus the given duration.
+///
+/// The duration is given as a list of `weeks`, `days`, `hours`, `minutes` or `seconds` arguments.
+///
+/// # Example
+///
+/// ```
+/// const EXPIRES_AT: time::OffsetDateTime = compile_time::expires_at!(days = 90);
+///
+/// assert_eq!(EXPIRES_AT, compile_time::datetime!() + time::Duration::days(90));
+/// assert_eq!(compile_time::expires_at!(weeks = 1, hours = 12), compile_time::expires_at!(days = 7, minutes = 720));
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn expires_at(item: TokenStream) -> TokenStream {
+  let DurationArgs { duration } = parse_macro_input!(item as DurationArgs);
+  let compile_time = try_compile!(get_compile_time());
+
+  let expires_at = match compile_time.checked_add(duration) {
+    Some(expires_at) => expires_at,
+    None => {
+      return syn::Error::new(proc_macro2::Span::call_site(), "expiry date is out of range").to_compile_error().into()
+    },
+  };
+
+  datetime_tokens(expires_at, Precision::Seconds).into()
+}
+
+/// Whether the build has expired as `bool`, i.e. whether the compile time plus the given duration has passed.
+///
+/// Accepts the same arguments as [`expires_at!`](expires_at!).
+/// This expands to an expression which is evaluated at runtime.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "deterministic"))]
+/// assert!(!compile_time::build_expired!(days = 90));
+/// assert!(compile_time::build_expired!(seconds = 0));
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn build_expired(item: TokenStream) -> TokenStream {
+  let expires_at = proc_macro2::TokenStream::from(expires_at(item));
+
+  quote! {
+    ::time::OffsetDateTime::now_utc() >= #expires_at
+  }
+  .into()
+}
+
+/// Assert that the crate is compiled before the given date or date and time.
+///
+/// The deadline is given either in `yyyy-MM-dd` or in RFC 3339 format, optionally followed by a
+/// custom message. Compilation fails if the compile time is on or after the deadline.
+///
+/// # Example
+///
+/// ```
+/// compile_time::assert_build_before!("9999-12-31");
+/// compile_time::assert_build_before!("9999-12-31T23:59:59Z", "Remove this workaround.");
+/// ```
+///
+/// ```compile_fail
+/// compile_time::assert_build_before!("1970-01-01", "Remove this workaround.");
+/// ```
+#[proc_macro]
+pub fn assert_build_before(item: TokenStream) -> TokenStream {
+  let AssertBuildBeforeArgs { deadline, message } = parse_macro_input!(item as AssertBuildBeforeArgs);
+  let compile_time = try_compile!(get_compile_time());
+
+  if compile_time < deadline.datetime {
+    return TokenStream::new();
+  }
+
+  let mut error = format!("build deadline `{}` has passed", deadline.lit.value());
+  if let Some(message) = message {
+    error = format!("{error}: {}", message.value());
+  }
+
+  syn::Error::new(deadline.lit.span(), error).to_compile_error().into()
+}
+
+/// Whether a time-limited build is still licensed as `bool`.
+///
+/// The expiry is given either in `yyyy-MM-dd` or in RFC 3339 format. Compilation fails if the
+/// compile time is on or after the expiry. At runtime, this evaluates to `true` until the expiry
+/// plus the optional `grace_days` has passed.
+///
+/// # Example
+///
+/// ```
+/// if !compile_time::licensed_until!("2999-12-31", grace_days = 14) {
+///   panic!("This evaluation build has expired.");
+/// }
+/// ```
+///
+/// ```compile_fail
+/// let licensed = compile_time::licensed_until!("1970-01-01");
+/// ```
+#[proc_macro]
+pub fn licensed_until(item: TokenStream) -> TokenStream {
+  let LicensedUntilArgs { expiry, grace_days } = parse_macro_input!(item as LicensedUntilArgs);
+  let compile_time = try_compile!(get_compile_time());
+
+  if compile_time >= expiry.datetime {
+    let error = format!("license expired on `{}`", expiry.lit.value());
+    return syn::Error::new(expiry.lit.span(), error).to_compile_error().into();
+  }
+
+  let grace_period = grace_days.checked_mul(86_400).map(time::Duration::seconds);
+  let Some(grace_period_end) = grace_period.and_then(|grace_period| expiry.datetime.checked_add(grace_period)) else {
+    return syn::Error::new(expiry.lit.span(), "grace period end is out of range").to_compile_error().into();
+  };
+  let Ok(grace_period_end) = u64::try_from(grace_period_end.unix_timestamp()) else {
+    return syn::Error::new(expiry.lit.span(), "grace period end is before the UNIX epoch").to_compile_error().into();
+  };
+
+  quote! {
+    ::std::time::SystemTime::now() < ::std::time::UNIX_EPOCH + ::core::time::Duration::from_secs(#grace_period_end)
+  }
+  .into()
+}
+
+/// Embed the compile date and time into the binary as `#[used]` static in a dedicated linker section.
+///
+/// The static contains `BUILD_TIMESTAMP=yyyy-MM-ddThh:mm:ssZ` and is placed in the `.ctime` section
+/// (`__DATA,__ctime` on Apple platforms), so the compile time can be recovered from the binary with
+/// tools like `strings` or `objcopy`, even if it is never used at runtime.
+///
+/// # Example
+///
+/// ```
+/// compile_time::embed_build_timestamp!();
+/// #
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn embed_build_timestamp(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let build_timestamp = format!("BUILD_TIMESTAMP={}", try_compile!(format_datetime(compile_time, None)));
+  let len = build_timestamp.len();
+  let build_timestamp = proc_macro2::Literal::byte_string(build_timestamp.as_bytes());
+
+  quote! {
+    #[doc(hidden)]
+    #[used]
+    #[cfg_attr(target_vendor = "apple", link_section = "__DATA,__ctime")]
+    #[cfg_attr(not(target_vendor = "apple"), link_section = ".ctime")]
+    static __COMPILE_TIME_BUILD_TIMESTAMP: [u8; #len] = *#build_timestamp;
+  }
+  .into()
+}
+
+/// Recompile the current crate on every build, so the compile time is always up to date.
+///
+/// Since `cargo` only recompiles a crate when its sources change, the compile time embedded by the
+/// other macros can otherwise be older than the latest build. Invoke this macro once, e.g. at the
+/// crate root, to opt into refreshing it on every build. It writes a file to `target/compile-time/`
+/// during macro expansion and includes it in the crate, which makes `cargo` consider the crate
+/// outdated on the next build.
+///
+/// # Example
+///
+/// ```
+/// compile_time::fresh!();
+/// #
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn fresh(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+  let dir = target::target_dir().unwrap_or_else(std::env::temp_dir).join("compile-time");
+  let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "unknown".into());
+  let path = dir.join(format!("{crate_name}.fresh"));
+
+  let result =
+    std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, compile_time.unix_timestamp_nanos().to_string()));
+  if let Err(err) = result {
+    let message = format!("failed to write {}: {}", path.display(), err);
+    return syn::Error::new(proc_macro2::Span::call_site(), message).to_compile_error().into();
+  }
+
+  let Some(path) = path.to_str() else {
+    let message = format!("path {} is not valid UTF-8", path.display());
+    return syn::Error::new(proc_macro2::Span::call_site(), message).to_compile_error().into();
   };
 
-  quote! {
-    ::semver::Version {
-      major: #major,
-      minor: #minor,
-      patch: #patch,
-      pre: #pre,
-      build: #build,
-    }
+  quote! {
+    const _: &[u8] = ::core::include_bytes!(#path);
+  }
+  .into()
+}
+
+/// Generate a test asserting that the embedded compile time does not depend on the wall clock.
+///
+/// The generated `#[test]` fails unless the `deterministic` feature is enabled and the embedded
+/// compile time matches `SOURCE_DATE_EPOCH` at build time (or the UNIX epoch if it is not set),
+/// so projects can enforce reproducible builds in their own test suite.
+///
+/// # Example
+///
+/// ```
+/// compile_time::generate_reproducibility_test!();
+/// #
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn generate_reproducibility_test(_item: TokenStream) -> TokenStream {
+  let compile_time = try_compile!(get_compile_time());
+
+  let deterministic = cfg!(feature = "deterministic");
+  let unix_timestamp = proc_macro2::Literal::i64_suffixed(compile_time.unix_timestamp());
+
+  quote! {
+    #[test]
+    fn compile_time_is_reproducible() {
+      ::core::assert!(
+        #deterministic,
+        "the compile time depends on the wall clock, enable the `deterministic` feature of `compile-time`",
+      );
+
+      let source_date_epoch = match ::core::option_env!("SOURCE_DATE_EPOCH") {
+        ::core::option::Option::Some(source_date_epoch) => source_date_epoch.trim().parse::<i64>().ok(),
+        ::core::option::Option::None => ::core::option::Option::Some(0),
+      };
+      ::core::assert_eq!(
+        ::core::option::Option::Some(#unix_timestamp),
+        source_date_epoch,
+        "the compile time does not match `SOURCE_DATE_EPOCH`",
+      );
+    }
+  }
+  .into()
+}
+
+/// Rust compiler version as `semver::Version`.
+///
+/// # Example
+///
+/// ```
+/// let rustc_version: semver::Version = compile_time::rustc_version!();
+/// assert_eq!(rustc_version, rustc_version::version().unwrap());
+/// ```
+#[proc_macro]
+pub fn rustc_version(_item: TokenStream) -> TokenStream {
+  version_tokens(try_compile!(get_rustc_version())).into()
+}
+
+/// Rust compiler version as `Option<semver::Version>`.
+///
+/// Expands to `None` instead of failing to compile if the version cannot be retrieved,
+/// regardless of the `lenient` feature.
+///
+/// # Example
+///
+/// ```
+/// let rustc_version: Option<semver::Version> = compile_time::try_rustc_version!();
+/// assert_eq!(rustc_version, rustc_version::version().ok());
+/// ```
+#[proc_macro]
+pub fn try_rustc_version(_item: TokenStream) -> TokenStream {
+  match &*RUSTC_VERSION_META {
+    Ok(rustc_version_meta) => {
+      let rustc_version = version_tokens(&rustc_version_meta.semver);
+      quote! { ::core::option::Option::Some(#rustc_version) }
+    },
+    Err(_) => quote! { ::core::option::Option::None::<::semver::Version> },
   }
   .into()
 }
 
 /// Rust compiler version as `&'static str`.
 ///
+/// Optionally, a `fallback` can be given, which is used instead of failing to compile if the
+/// version cannot be retrieved.
+///
 /// # Example
 ///
 /// ```
 /// const RUSTC_VERSION_STRING: &str = compile_time::rustc_version_str!();
-/// assert_eq!(RUSTC_VERSION_STRING, compile_time::rustc_version_str!());
+/// assert_eq!(RUSTC_VERSION_STRING, compile_time::rustc_version_str!(fallback = "unknown"));
 /// ```
 #[proc_macro]
-pub fn rustc_version_str(_item: TokenStream) -> TokenStream {
-  let rustc_version = match &*RUSTC_VERSION {
-    Ok(rustc_version) => rustc_version,
-    Err(err) => panic!("Failed to get version: {}", err),
+pub fn rustc_version_str(item: TokenStream) -> TokenStream {
+  let FallbackArgs { fallback } = parse_macro_input!(item as FallbackArgs);
+
+  let rustc_version_string = match (&*RUSTC_VERSION_META, fallback) {
+    (Err(_), Some(fallback)) => fallback.value(),
+    _ => try_compile!(get_rustc_version()).to_string(),
   };
 
-  let rustc_version_string = rustc_version.to_string();
   quote! { #rustc_version_string }.into()
 }
 
+/// Rust compiler version as `Option<&'static str>`.
+///
+/// Expands to `None` instead of failing to compile if the version cannot be retrieved,
+/// regardless of the `lenient` feature.
+///
+/// # Example
+///
+/// ```
+/// const RUSTC_VERSION_STRING: Option<&str> = compile_time::try_rustc_version_str!();
+/// assert_eq!(RUSTC_VERSION_STRING, Some(compile_time::rustc_version_str!()));
+/// ```
+#[proc_macro]
+pub fn try_rustc_version_str(_item: TokenStream) -> TokenStream {
+  match &*RUSTC_VERSION_META {
+    Ok(rustc_version_meta) => {
+      let rustc_version_string = rustc_version_meta.semver.to_string();
+      quote! { ::core::option::Option::Some(#rustc_version_string) }
+    },
+    Err(_) => quote! { ::core::option::Option::None::<&'static str> },
+  }
+  .into()
+}
+
 /// Rust compiler major version as integer literal.
 ///
 /// # Example
@@ -314,10 +2104,7 @@ pub fn rustc_version_str(_item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn rustc_version_major(_item: TokenStream) -> TokenStream {
-  let major = match &*RUSTC_VERSION {
-    Ok(rustc_version) => rustc_version.major,
-    Err(err) => panic!("Failed to get version: {}", err),
-  };
+  let major = try_compile!(get_rustc_version()).major;
 
   proc_macro2::Literal::u64_unsuffixed(major).to_token_stream().into()
 }
@@ -332,10 +2119,7 @@ pub fn rustc_version_major(_item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn rustc_version_minor(_item: TokenStream) -> TokenStream {
-  let minor = match &*RUSTC_VERSION {
-    Ok(rustc_version) => rustc_version.minor,
-    Err(err) => panic!("Failed to get version: {}", err),
-  };
+  let minor = try_compile!(get_rustc_version()).minor;
 
   proc_macro2::Literal::u64_unsuffixed(minor).to_token_stream().into()
 }
@@ -350,10 +2134,7 @@ pub fn rustc_version_minor(_item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn rustc_version_patch(_item: TokenStream) -> TokenStream {
-  let patch = match &*RUSTC_VERSION {
-    Ok(rustc_version) => rustc_version.patch,
-    Err(err) => panic!("Failed to get version: {}", err),
-  };
+  let patch = try_compile!(get_rustc_version()).patch;
 
   proc_macro2::Literal::u64_unsuffixed(patch).to_token_stream().into()
 }
@@ -368,10 +2149,7 @@ pub fn rustc_version_patch(_item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn rustc_version_pre(_item: TokenStream) -> TokenStream {
-  let pre = match &*RUSTC_VERSION {
-    Ok(rustc_version) => rustc_version.pre.as_str(),
-    Err(err) => panic!("Failed to get version: {}", err),
-  };
+  let pre = try_compile!(get_rustc_version()).pre.as_str();
 
   quote! { #pre }.into()
 }
@@ -386,10 +2164,505 @@ pub fn rustc_version_pre(_item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn rustc_version_build(_item: TokenStream) -> TokenStream {
-  let build = match &*RUSTC_VERSION {
-    Ok(rustc_version) => rustc_version.build.as_str(),
-    Err(err) => panic!("Failed to get version: {}", err),
-  };
+  let build = try_compile!(get_rustc_version()).build.as_str();
 
   quote! { #build }.into()
 }
+
+/// Whether the Rust compiler version matches the given `semver::VersionReq` as boolean literal.
+///
+/// Pre-release versions, e.g. `1.80.0-nightly`, are matched as if they were the corresponding
+/// release version, so `>=1.75` also matches nightly and beta compilers.
+///
+/// # Example
+///
+/// ```
+/// if compile_time::rustc_version_matches!(">=1.60") {
+///   println!("Compiled using Rust 1.60 or newer.");
+/// }
+///
+/// let rustc_version: semver::Version = compile_time::rustc_version!();
+/// assert_eq!(compile_time::rustc_version_matches!("<1.0"), rustc_version.major < 1);
+/// ```
+///
+/// ```compile_fail
+/// compile_time::rustc_version_matches!("1.x.y");
+/// ```
+#[proc_macro]
+pub fn rustc_version_matches(item: TokenStream) -> TokenStream {
+  let version_req = parse_macro_input!(item as syn::LitStr);
+  let rustc_version = try_compile!(get_rustc_version());
+
+  let matches = match semver::VersionReq::parse(&version_req.value()) {
+    Ok(req) => req.matches(&release_version(rustc_version)),
+    Err(err) => {
+      return syn::Error::new(version_req.span(), format!("invalid version requirement: {err}"))
+        .to_compile_error()
+        .into()
+    },
+  };
+
+  quote! { #matches }.into()
+}
+
+/// Release channel of the Rust compiler as `&'static str`, i.e. `stable`, `beta`, `nightly` or `dev`.
+///
+/// # Example
+///
+/// ```
+/// let rustc_channel: &str = compile_time::rustc_channel_str!();
+/// assert!(["stable", "beta", "nightly", "dev"].contains(&rustc_channel));
+/// ```
+#[proc_macro]
+pub fn rustc_channel_str(_item: TokenStream) -> TokenStream {
+  let channel = match try_compile!(get_rustc_version_meta()).channel {
+    rustc_version::Channel::Stable => "stable",
+    rustc_version::Channel::Beta => "beta",
+    rustc_version::Channel::Nightly => "nightly",
+    rustc_version::Channel::Dev => "dev",
+  };
+
+  quote! { #channel }.into()
+}
+
+/// Whether the Rust compiler is a nightly compiler as boolean literal.
+///
+/// # Example
+///
+/// ```
+/// const NIGHTLY: bool = compile_time::rustc_is_nightly!();
+/// assert_eq!(NIGHTLY, compile_time::rustc_channel_str!() == "nightly");
+/// ```
+#[proc_macro]
+pub fn rustc_is_nightly(_item: TokenStream) -> TokenStream {
+  let is_nightly = try_compile!(get_rustc_version_meta()).channel == rustc_version::Channel::Nightly;
+
+  quote! { #is_nightly }.into()
+}
+
+/// Whether the Rust compiler is a beta compiler as boolean literal.
+///
+/// # Example
+///
+/// ```
+/// const BETA: bool = compile_time::rustc_is_beta!();
+/// assert_eq!(BETA, compile_time::rustc_channel_str!() == "beta");
+/// ```
+#[proc_macro]
+pub fn rustc_is_beta(_item: TokenStream) -> TokenStream {
+  let is_beta = try_compile!(get_rustc_version_meta()).channel == rustc_version::Channel::Beta;
+
+  quote! { #is_beta }.into()
+}
+
+/// Whether the Rust compiler is a stable compiler as boolean literal.
+///
+/// # Example
+///
+/// ```
+/// const STABLE: bool = compile_time::rustc_is_stable!();
+/// assert_eq!(STABLE, compile_time::rustc_channel_str!() == "stable");
+/// ```
+#[proc_macro]
+pub fn rustc_is_stable(_item: TokenStream) -> TokenStream {
+  let is_stable = try_compile!(get_rustc_version_meta()).channel == rustc_version::Channel::Stable;
+
+  quote! { #is_stable }.into()
+}
+
+/// Assert that the crate is compiled with exactly the given Rust compiler version.
+///
+/// The expected version is given either as string literal or as `env = "NAME"`, in which case it is
+/// read from the environment variable `NAME`. Compilation fails if the version of the Rust compiler
+/// differs, including its pre-release part, e.g. `1.80.0-nightly`. With the `lenient` feature,
+/// nothing is asserted if the version cannot be retrieved.
+///
+/// # Example
+///
+/// ```ignore
+/// compile_time::assert_same_rustc_as!("1.79.0");
+/// compile_time::assert_same_rustc_as!(env = "RUSTC_PINNED_VERSION");
+/// ```
+///
+/// ```compile_fail
+/// compile_time::assert_same_rustc_as!("0.1.0");
+/// ```
+#[proc_macro]
+pub fn assert_same_rustc_as(item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(item as AssertSameRustcArgs);
+
+  let (expected, lit, tracking) = match &args {
+    AssertSameRustcArgs::Version(lit) => (lit.value(), lit, None),
+    AssertSameRustcArgs::Env(lit) => match std::env::var(lit.value()) {
+      // Read the variable using `env!` as well so that the compiler tracks it.
+      Ok(expected) => (expected, lit, Some(quote! { const _: &str = ::core::env!(#lit); })),
+      Err(err) => {
+        return syn::Error::new(lit.span(), format!("failed to read `{}`: {}", lit.value(), err))
+          .to_compile_error()
+          .into()
+      },
+    },
+  };
+
+  let expected = match semver::Version::parse(expected.trim()) {
+    Ok(expected) => expected,
+    Err(err) => {
+      return syn::Error::new(lit.span(), format!("invalid version `{expected}`: {err}")).to_compile_error().into()
+    },
+  };
+
+  let Some(rustc_version) = try_compile!(get_known_rustc_version()) else {
+    return tracking.unwrap_or_default().into();
+  };
+
+  if *rustc_version != expected {
+    let error = format!("expected Rust compiler version `{expected}`, found `{rustc_version}`");
+    return syn::Error::new(lit.span(), error).to_compile_error().into();
+  }
+
+  tracking.unwrap_or_default().into()
+}
+
+/// Git commit hash of the Rust compiler as `&'static str`.
+///
+/// Expands to an empty string if the commit hash is unknown, e.g. for locally built compilers.
+///
+/// # Example
+///
+/// ```
+/// let commit_hash: &str = compile_time::rustc_commit_hash!();
+/// assert!(commit_hash.chars().all(|c| c.is_ascii_hexdigit()));
+/// ```
+#[proc_macro]
+pub fn rustc_commit_hash(_item: TokenStream) -> TokenStream {
+  let commit_hash = try_compile!(get_rustc_version_meta()).commit_hash.as_deref().unwrap_or_default();
+
+  quote! { #commit_hash }.into()
+}
+
+/// Abbreviated Git commit hash of the Rust compiler as `&'static str`, as shown by `rustc --version`.
+///
+/// Expands to an empty string if the commit hash is unknown, e.g. for locally built compilers.
+///
+/// # Example
+///
+/// ```
+/// let commit_hash_short: &str = compile_time::rustc_commit_hash_short!();
+/// assert!(compile_time::rustc_commit_hash!().starts_with(commit_hash_short));
+/// ```
+#[proc_macro]
+pub fn rustc_commit_hash_short(_item: TokenStream) -> TokenStream {
+  let commit_hash = try_compile!(get_rustc_version_meta()).commit_hash.as_deref().unwrap_or_default();
+  let commit_hash_short = commit_hash.get(..9).unwrap_or(commit_hash);
+
+  quote! { #commit_hash_short }.into()
+}
+
+/// Commit date of the Rust compiler as `&'static str` in `yyyy-MM-dd` format.
+///
+/// Expands to an empty string if the commit date is unknown, e.g. for locally built compilers.
+///
+/// # Example
+///
+/// ```
+/// let commit_date: &str = compile_time::rustc_commit_date_str!();
+/// assert!(commit_date.is_empty() || commit_date.len() == 10);
+/// ```
+#[proc_macro]
+pub fn rustc_commit_date_str(_item: TokenStream) -> TokenStream {
+  let commit_date = try_compile!(get_rustc_version_meta()).commit_date.as_deref().unwrap_or_default();
+
+  quote! { #commit_date }.into()
+}
+
+/// Commit date of the Rust compiler as `time::Date`.
+///
+/// Compilation fails if the commit date is unknown, e.g. for locally built compilers.
+///
+/// # Example
+///
+/// ```
+/// const COMMIT_DATE: time::Date = compile_time::rustc_commit_date!();
+///
+/// let format = time::macros::format_description!("[year]-[month]-[day]");
+/// assert_eq!(COMMIT_DATE.format(&format).unwrap(), compile_time::rustc_commit_date_str!());
+/// ```
+#[cfg(feature = "time-types")]
+#[proc_macro]
+pub fn rustc_commit_date(_item: TokenStream) -> TokenStream {
+  let commit_date = try_compile!(get_rustc_version_meta()).commit_date.as_deref().unwrap_or_default();
+
+  match time::Date::parse(commit_date, format_description!("[year]-[month]-[day]")) {
+    Ok(commit_date) => date_tokens(commit_date).into(),
+    Err(_) => syn::Error::new(proc_macro2::Span::call_site(), "commit date of the Rust compiler is unknown")
+      .to_compile_error()
+      .into(),
+  }
+}
+
+/// Host target triple of the Rust compiler as `&'static str`, e.g. `x86_64-unknown-linux-gnu`.
+///
+/// # Example
+///
+/// ```
+/// let host: &str = compile_time::rustc_host_triple_str!();
+/// println!("Compiled on {host}.");
+/// ```
+#[proc_macro]
+pub fn rustc_host_triple_str(_item: TokenStream) -> TokenStream {
+  let host = try_compile!(get_rustc_version_meta()).host.as_str();
+
+  quote! { #host }.into()
+}
+
+/// LLVM version of the Rust compiler as `semver::Version`.
+///
+/// Only the major and minor version are reported by the Rust compiler, so the patch version is
+/// always `0`. Compilation fails if the LLVM version is unknown, e.g. for compilers using a
+/// different code generation backend.
+///
+/// # Example
+///
+/// ```
+/// let llvm_version: semver::Version = compile_time::rustc_llvm_version!();
+/// assert_eq!(llvm_version.patch, 0);
+/// ```
+#[proc_macro]
+pub fn rustc_llvm_version(_item: TokenStream) -> TokenStream {
+  let rustc_version_meta = try_compile!(get_rustc_version_meta());
+  let Some(rustc_version::LlvmVersion { major, minor }) = rustc_version_meta.llvm_version else {
+    return syn::Error::new(proc_macro2::Span::call_site(), "LLVM version of the Rust compiler is unknown")
+      .to_compile_error()
+      .into();
+  };
+
+  quote! {
+    ::semver::Version::new(#major, #minor, 0)
+  }
+  .into()
+}
+
+/// LLVM version of the Rust compiler as `&'static str` in `major.minor` format.
+///
+/// Expands to an empty string if the LLVM version is unknown, e.g. for compilers using a
+/// different code generation backend.
+///
+/// # Example
+///
+/// ```
+/// let llvm_version: semver::Version = compile_time::rustc_llvm_version!();
+/// assert_eq!(compile_time::rustc_llvm_version_str!(), format!("{}.{}", llvm_version.major, llvm_version.minor));
+/// ```
+#[proc_macro]
+pub fn rustc_llvm_version_str(_item: TokenStream) -> TokenStream {
+  let llvm_version =
+    try_compile!(get_rustc_version_meta()).llvm_version.as_ref().map(ToString::to_string).unwrap_or_default();
+
+  quote! { #llvm_version }.into()
+}
+
+/// Keep the annotated item only if the Rust compiler version satisfies all given conditions.
+///
+/// This is a replacement for the unstable `cfg(version(...))`. The conditions are given as
+/// `since = "1.75"` (version or newer), `before = "1.80"` (older than version) or
+/// `matches = ">=1.70, <1.80"` (any `semver::VersionReq`). Pre-release versions, e.g.
+/// `1.80.0-nightly`, are matched as if they were the corresponding release version.
+///
+/// Compilation fails if the version cannot be retrieved, even with the `lenient` feature, since
+/// neither keeping nor removing the item would be correct in that case.
+///
+/// # Example
+///
+/// ```
+/// #[compile_time::cfg_rustc(since = "1.60")]
+/// fn rustc_info() -> &'static str {
+///   "Rust 1.60 or newer"
+/// }
+///
+/// #[compile_time::cfg_rustc(before = "1.60")]
+/// fn rustc_info() -> &'static str {
+///   "Rust older than 1.60"
+/// }
+///
+/// assert_eq!(rustc_info(), "Rust 1.60 or newer");
+/// ```
+#[proc_macro_attribute]
+pub fn cfg_rustc(attr: TokenStream, item: TokenStream) -> TokenStream {
+  let CfgRustcArgs { requirements } = parse_macro_input!(attr as CfgRustcArgs);
+
+  let Some(rustc_version) = try_compile!(get_known_rustc_version()) else {
+    return syn::Error::new(
+      proc_macro2::Span::call_site(),
+      "cannot evaluate `#[cfg_rustc]` since the Rust compiler version is unknown",
+    )
+    .to_compile_error()
+    .into();
+  };
+
+  let rustc_version = release_version(rustc_version);
+  if requirements.iter().all(|requirement| requirement.matches(&rustc_version)) {
+    item
+  } else {
+    TokenStream::new()
+  }
+}
+
+/// Assert that the Rust compiler version satisfies the given `semver::VersionReq`.
+///
+/// Compilation fails if the version does not match. Pre-release versions, e.g. `1.80.0-nightly`,
+/// are matched as if they were the corresponding release version. With the `lenient` feature,
+/// nothing is asserted if the version cannot be retrieved.
+///
+/// # Example
+///
+/// ```
+/// compile_time::assert_rustc_version!(">=1.60");
+/// ```
+///
+/// ```compile_fail
+/// compile_time::assert_rustc_version!(">=1.0, <1.1");
+/// ```
+#[proc_macro]
+pub fn assert_rustc_version(item: TokenStream) -> TokenStream {
+  let version_req = parse_macro_input!(item as syn::LitStr);
+
+  let req = match semver::VersionReq::parse(&version_req.value()) {
+    Ok(req) => req,
+    Err(err) => {
+      return syn::Error::new(version_req.span(), format!("invalid version requirement: {err}"))
+        .to_compile_error()
+        .into()
+    },
+  };
+
+  let Some(rustc_version) = try_compile!(get_known_rustc_version()) else { return TokenStream::new() };
+  if req.matches(&release_version(rustc_version)) {
+    return TokenStream::new();
+  }
+
+  let error = format!("Rust compiler version `{rustc_version}` does not satisfy `{req}`");
+  syn::Error::new(version_req.span(), error).to_compile_error().into()
+}
+
+/// Rust compiler version metadata as `rustc_version::VersionMeta`.
+///
+/// This requires a dependency on the `rustc_version` and `semver` crates in your own `Cargo.toml`.
+///
+/// # Example
+///
+/// ```
+/// let rustc_version_meta: rustc_version::VersionMeta = compile_time::rustc_version_meta!();
+/// assert_eq!(rustc_version_meta, rustc_version::version_meta().unwrap());
+/// ```
+#[proc_macro]
+pub fn rustc_version_meta(_item: TokenStream) -> TokenStream {
+  fn string_tokens(s: &str) -> proc_macro2::TokenStream {
+    quote! { ::std::string::String::from(#s) }
+  }
+
+  fn option_tokens<T>(option: Option<T>, f: impl FnOnce(T) -> proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match option.map(f) {
+      Some(tokens) => quote! { ::core::option::Option::Some(#tokens) },
+      None => quote! { ::core::option::Option::None },
+    }
+  }
+
+  let rustc_version::VersionMeta {
+    semver,
+    commit_hash,
+    commit_date,
+    build_date,
+    channel,
+    host,
+    short_version_string,
+    llvm_version,
+  } = try_compile!(get_rustc_version_meta());
+
+  let semver = version_tokens(semver);
+  let commit_hash = option_tokens(commit_hash.as_deref(), string_tokens);
+  let commit_date = option_tokens(commit_date.as_deref(), string_tokens);
+  let build_date = option_tokens(build_date.as_deref(), string_tokens);
+  let channel = format_ident!("{}", format!("{:?}", channel));
+  let host = string_tokens(host);
+  let short_version_string = string_tokens(short_version_string);
+  let llvm_version = option_tokens(llvm_version.as_ref(), |rustc_version::LlvmVersion { major, minor }| {
+    quote! { ::rustc_version::LlvmVersion { major: #major, minor: #minor } }
+  });
+
+  quote! {
+    ::rustc_version::VersionMeta {
+      semver: #semver,
+      commit_hash: #commit_hash,
+      commit_date: #commit_date,
+      build_date: #build_date,
+      channel: ::rustc_version::Channel::#channel,
+      host: #host,
+      short_version_string: #short_version_string,
+      llvm_version: #llvm_version,
+    }
+  }
+  .into()
+}
+
+/// Assert that the Rust compiler is at least the minimum supported Rust version of the current crate.
+///
+/// The minimum supported Rust version is read from the `rust-version` field in the crate's
+/// `Cargo.toml`. Compilation fails if the Rust compiler is older. If the field is missing, this
+/// expands to nothing, unless `warn_missing` is given, in which case a warning is emitted instead.
+/// With the `lenient` feature, nothing is asserted if the Rust compiler version cannot be retrieved.
+///
+/// # Example
+///
+/// ```
+/// compile_time::assert_msrv!();
+/// ```
+#[proc_macro]
+pub fn assert_msrv(item: TokenStream) -> TokenStream {
+  let warn_missing = parse_macro_input!(item as Option<Ident>);
+  if let Some(ident) = &warn_missing {
+    if ident != "warn_missing" {
+      return syn::Error::new(ident.span(), "expected `warn_missing`").to_compile_error().into();
+    }
+  }
+
+  // Read the variable using `option_env!` as well so that the compiler tracks it.
+  let tracking = quote! { const _: ::core::option::Option<&str> = ::core::option_env!("CARGO_PKG_RUST_VERSION"); };
+
+  let rust_version = std::env::var("CARGO_PKG_RUST_VERSION").unwrap_or_default();
+  if rust_version.is_empty() {
+    if warn_missing.is_none() {
+      return tracking.into();
+    }
+
+    return quote! {
+      #tracking
+      const _: () = {
+        #[deprecated(note = "`rust-version` is not specified in `Cargo.toml`")]
+        const MISSING_RUST_VERSION: () = ();
+        MISSING_RUST_VERSION
+      };
+    }
+    .into();
+  }
+
+  let req = match semver::VersionReq::parse(&format!(">={rust_version}")) {
+    Ok(req) => req,
+    Err(err) => {
+      let error = format!("invalid `rust-version` `{rust_version}`: {err}");
+      return syn::Error::new(proc_macro2::Span::call_site(), error).to_compile_error().into();
+    },
+  };
+
+  let Some(rustc_version) = try_compile!(get_known_rustc_version()) else {
+    return tracking.into();
+  };
+
+  if req.matches(&release_version(rustc_version)) {
+    return tracking.into();
+  }
+
+  let error = format!(
+    "Rust compiler version `{rustc_version}` is older than the minimum supported Rust version `{rust_version}`"
+  );
+  syn::Error::new(proc_macro2::Span::call_site(), error).to_compile_error().into()
+}
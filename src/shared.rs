@@ -0,0 +1,121 @@
+//! Compile time shared between all crates compiled by the same `cargo` invocation.
+//!
+//! The first proc-macro server capturing the compile time persists it to a file in
+//! `target/<profile>/compile-time/`, keyed by the jobserver `cargo` passes to every `rustc` process it spawns.
+//! All subsequent captures in the same `cargo` invocation read it back from there.
+
+use std::{fs, io, path::Path};
+
+use time::OffsetDateTime;
+
+use crate::target::host_profile_dir;
+
+const EXTENSION: &str = "timestamp";
+
+/// Get the compile time shared with all other crates in this build, capturing it if necessary.
+///
+/// Returns `None` if there is no build to share the compile time with, e.g. when running
+/// outside of `cargo` or on an unsupported platform.
+pub fn compile_time() -> io::Result<Option<OffsetDateTime>> {
+  let (Some(profile_dir), Some(build_id)) = (host_profile_dir(), build_id()) else { return Ok(None) };
+
+  let dir = profile_dir.join("compile-time");
+  fs::create_dir_all(&dir)?;
+
+  let path = dir.join(format!("{build_id}.{EXTENSION}"));
+
+  match read(&path) {
+    Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+    result => return result.map(Some),
+  }
+
+  let now = OffsetDateTime::now_utc();
+
+  // Write to a temporary file first and then hard-link it, which fails if another process
+  // was faster, so that the file is only ever visible with its complete contents.
+  let tmp_path = dir.join(format!("{build_id}.{}.tmp", std::process::id()));
+  fs::write(&tmp_path, now.unix_timestamp_nanos().to_string())?;
+  let result = fs::hard_link(&tmp_path, &path);
+  fs::remove_file(&tmp_path)?;
+
+  match result {
+    Ok(()) => {
+      remove_stale(&dir, &path);
+      Ok(Some(now))
+    },
+    Err(err) if err.kind() == io::ErrorKind::AlreadyExists => read(&path).map(Some),
+    Err(err) => Err(err),
+  }
+}
+
+fn read(path: &Path) -> io::Result<OffsetDateTime> {
+  let contents = fs::read_to_string(path)?;
+
+  contents
+    .trim()
+    .parse::<i128>()
+    .ok()
+    .and_then(|nanos| OffsetDateTime::from_unix_timestamp_nanos(nanos).ok())
+    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("invalid timestamp in {}", path.display())))
+}
+
+/// Remove files left over by previous builds. Cargo holds a lock on the profile directory
+/// for the whole build, so there cannot be any other build using them concurrently.
+fn remove_stale(dir: &Path, current: &Path) {
+  let Ok(entries) = fs::read_dir(dir) else { return };
+
+  for entry in entries.flatten() {
+    let path = entry.path();
+
+    if path != current && path.extension().is_some_and(|extension| extension == EXTENSION) {
+      let _ = fs::remove_file(path);
+    }
+  }
+}
+
+/// Get an ID which is unique for the `cargo` invocation as long as it is running.
+///
+/// The jobserver is created by `cargo` for each invocation and inherited by all `rustc` processes,
+/// as opposed to e.g. the parent process ID, which is reused and not the `cargo` process when
+/// using a `RUSTC_WRAPPER` like `sccache`.
+fn build_id() -> Option<String> {
+  let makeflags = std::env::var("CARGO_MAKEFLAGS").ok()?;
+
+  let auth = makeflags
+    .split_whitespace()
+    .rev()
+    .find_map(|flag| flag.strip_prefix("--jobserver-auth=").or_else(|| flag.strip_prefix("--jobserver-fds=")))?;
+
+  jobserver_id(auth)
+}
+
+#[cfg(unix)]
+fn jobserver_id(auth: &str) -> Option<String> {
+  use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+  let metadata = if let Some(path) = auth.strip_prefix("fifo:") {
+    fs::metadata(path).ok()?
+  } else {
+    let (read_fd, _) = auth.split_once(',')?;
+    let read_fd = read_fd.parse::<u32>().ok()?;
+    fs::metadata(format!("/dev/fd/{read_fd}")).ok()?
+  };
+
+  // The file descriptors are only inherited when `rustc` is spawned by `cargo` directly.
+  if !metadata.file_type().is_fifo() {
+    return None;
+  }
+
+  Some(format!("{:x}-{:x}", metadata.dev(), metadata.ino()))
+}
+
+#[cfg(windows)]
+fn jobserver_id(auth: &str) -> Option<String> {
+  // The jobserver is a named semaphore with a random name.
+  auth.chars().all(|c| c.is_ascii_alphanumeric() || c == '_').then(|| auth.to_owned())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn jobserver_id(_auth: &str) -> Option<String> {
+  None
+}
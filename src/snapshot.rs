@@ -0,0 +1,102 @@
+//! Snapshot of the values resolved during macro expansion, written to
+//! `target/<profile>/compile-time/<package>/<crate>.<kind>.json` when the `COMPILE_TIME_SNAPSHOT`
+//! environment variable is set to `1`.
+
+use std::{env, fmt::Write, fs, io, sync::Mutex};
+
+use time::{format_description::well_known::Rfc3339, OffsetDateTime, UtcOffset};
+
+use crate::target::{crate_kind, profile_dir};
+
+#[derive(Default)]
+struct Snapshot {
+  compile_time: Option<OffsetDateTime>,
+  local_offset: Option<UtcOffset>,
+  rustc_version: Option<String>,
+}
+
+static SNAPSHOT: Mutex<Snapshot> = Mutex::new(Snapshot { compile_time: None, local_offset: None, rustc_version: None });
+
+pub fn record_compile_time(compile_time: OffsetDateTime) -> Result<(), String> {
+  record(|snapshot| snapshot.compile_time = Some(compile_time))
+}
+
+pub fn record_local_offset(local_offset: UtcOffset) -> Result<(), String> {
+  record(|snapshot| snapshot.local_offset = Some(local_offset))
+}
+
+pub fn record_rustc_version(rustc_version: &rustc_version::Version) -> Result<(), String> {
+  record(|snapshot| snapshot.rustc_version = Some(rustc_version.to_string()))
+}
+
+fn record(f: impl FnOnce(&mut Snapshot)) -> Result<(), String> {
+  if env::var("COMPILE_TIME_SNAPSHOT").as_deref() != Ok("1") {
+    return Ok(());
+  }
+
+  let mut snapshot = SNAPSHOT.lock().unwrap_or_else(|err| err.into_inner());
+  f(&mut snapshot);
+
+  match write(&snapshot) {
+    Ok(()) => Ok(()),
+    #[cfg(feature = "lenient")]
+    Err(_) => Ok(()),
+    #[cfg(not(feature = "lenient"))]
+    Err(err) => Err(format!("failed to write snapshot: {err}")),
+  }
+}
+
+fn write(snapshot: &Snapshot) -> io::Result<()> {
+  let Some(profile_dir) = profile_dir() else { return Ok(()) };
+  let package_name = env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "unknown".into());
+  let crate_name = env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "unknown".into());
+  let crate_kind = crate_kind();
+
+  let mut fields = vec![
+    ("package", json_string(&package_name)),
+    ("crate", json_string(&crate_name)),
+    ("kind", json_string(&crate_kind)),
+  ];
+
+  if let Some(compile_time) = snapshot.compile_time {
+    let datetime = compile_time.format(&Rfc3339).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    fields.push(("datetime", json_string(&datetime)));
+    fields.push(("unix", compile_time.unix_timestamp().to_string()));
+  }
+
+  if let Some(local_offset) = snapshot.local_offset {
+    fields.push(("utc_offset_seconds", local_offset.whole_seconds().to_string()));
+  }
+
+  if let Some(rustc_version) = &snapshot.rustc_version {
+    fields.push(("rustc_version", json_string(rustc_version)));
+  }
+
+  let mut json = String::from("{\n");
+  for (i, (key, value)) in fields.iter().enumerate() {
+    let separator = if i + 1 < fields.len() { "," } else { "" };
+    writeln!(json, "  {}: {value}{separator}", json_string(key)).unwrap();
+  }
+  json.push_str("}\n");
+
+  let dir = profile_dir.join("compile-time").join(package_name);
+  fs::create_dir_all(&dir)?;
+  fs::write(dir.join(format!("{crate_name}.{crate_kind}.json")), json)
+}
+
+fn json_string(s: &str) -> String {
+  let mut json = String::with_capacity(s.len() + 2);
+
+  json.push('"');
+  for c in s.chars() {
+    match c {
+      '"' => json.push_str("\\\""),
+      '\\' => json.push_str("\\\\"),
+      c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+      c => json.push(c),
+    }
+  }
+  json.push('"');
+
+  json
+}
@@ -0,0 +1,91 @@
+use std::{
+  env,
+  path::{Path, PathBuf},
+};
+
+/// Find the target directory based on the `--out-dir` argument passed to `rustc`.
+///
+/// Returns `None` if `rustc` was not invoked with `--out-dir`, e.g. when running outside of `cargo`.
+pub fn target_dir() -> Option<PathBuf> {
+  let out_dir = out_dir()?;
+
+  Some(find_target_dir(&out_dir).to_owned())
+}
+
+/// Find the profile directory, e.g. `target/debug` or `target/<triple>/release`, based on the
+/// `--out-dir` argument passed to `rustc`.
+///
+/// Returns `None` if `rustc` was not invoked by `cargo`.
+pub fn profile_dir() -> Option<PathBuf> {
+  let out_dir = out_dir()?;
+
+  Some(find_profile_dir(&out_dir)?.to_owned())
+}
+
+/// Find the profile directory of the host, e.g. `target/debug`, based on the `--out-dir` argument
+/// passed to `rustc`.
+///
+/// `cargo` locks the profile directory for the whole build. When cross-compiling, this is
+/// `target/debug` instead of `target/<triple>/debug`, since `cargo` locks both and crates compiled
+/// for the host and the target are part of the same build.
+///
+/// Returns `None` if `rustc` was not invoked by `cargo`.
+#[cfg(feature = "shared-time")]
+pub fn host_profile_dir() -> Option<PathBuf> {
+  let out_dir = out_dir()?;
+  let profile_dir = find_profile_dir(&out_dir)?;
+
+  // When cross-compiling, the profile directory is `target/<triple>/<profile>`.
+  let host_profile_dir = profile_dir
+    .parent()
+    .filter(|triple_dir| triple_dir.join("CACHEDIR.TAG").is_file())
+    .and_then(Path::parent)
+    .zip(profile_dir.file_name())
+    .map(|(target_dir, profile)| target_dir.join(profile))
+    .filter(|host_profile_dir| host_profile_dir.join(".cargo-lock").is_file());
+
+  Some(host_profile_dir.unwrap_or_else(|| profile_dir.to_owned()))
+}
+
+/// Get the kind of the crate being compiled based on the arguments passed to `rustc`, i.e. its
+/// crate type, e.g. `bin` or `lib`, or `test` for test harnesses.
+pub fn crate_kind() -> String {
+  let mut crate_type = None;
+
+  let mut args = env::args_os();
+  while let Some(arg) = args.next() {
+    if arg == "--test" {
+      return "test".into();
+    } else if arg == "--crate-type" {
+      crate_type = crate_type.or_else(|| args.next().and_then(|arg| arg.into_string().ok()));
+    } else if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix("--crate-type=")) {
+      crate_type = crate_type.or_else(|| Some(value.to_owned()));
+    }
+  }
+
+  crate_type.unwrap_or_else(|| "bin".into())
+}
+
+fn out_dir() -> Option<PathBuf> {
+  let mut args = env::args_os();
+
+  loop {
+    let arg = args.next()?;
+
+    if arg == "--out-dir" {
+      if let Some(out_dir) = args.next() {
+        return Some(PathBuf::from(out_dir));
+      }
+    } else if let Some(out_dir) = arg.to_str().and_then(|arg| arg.strip_prefix("--out-dir=")) {
+      return Some(PathBuf::from(out_dir));
+    }
+  }
+}
+
+fn find_target_dir(out_dir: &Path) -> &Path {
+  out_dir.ancestors().find(|dir| dir.join("CACHEDIR.TAG").is_file()).unwrap_or(out_dir)
+}
+
+fn find_profile_dir(out_dir: &Path) -> Option<&Path> {
+  out_dir.ancestors().find(|dir| dir.join(".cargo-lock").is_file())
+}